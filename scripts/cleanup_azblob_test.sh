@@ -0,0 +1,21 @@
+#!/bin/bash
+
+# Azure Blob测试环境清理脚本
+
+set -e
+
+echo "正在清理Azure Blob测试环境..."
+
+# 停止并删除Azurite模拟器
+if docker ps | grep -q azurite-test-server; then
+    echo "停止Azurite模拟器..."
+    docker stop azurite-test-server
+    echo "✅ Azurite模拟器已停止"
+else
+    echo "ℹ️  Azurite模拟器未在运行"
+fi
+
+# 清理可能存在的悬挂容器
+docker ps -a | grep azurite-test-server | awk '{print $1}' | xargs -r docker rm
+
+echo "✅ Azure Blob测试环境清理完成"
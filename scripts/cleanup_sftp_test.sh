@@ -0,0 +1,21 @@
+#!/bin/bash
+
+# SFTP测试环境清理脚本
+
+set -e
+
+echo "正在清理SFTP测试环境..."
+
+# 停止并删除SFTP测试服务器
+if docker ps | grep -q sftp-test-server; then
+    echo "停止SFTP测试服务器..."
+    docker stop sftp-test-server
+    echo "✅ SFTP测试服务器已停止"
+else
+    echo "ℹ️  SFTP测试服务器未在运行"
+fi
+
+# 清理可能存在的悬挂容器
+docker ps -a | grep sftp-test-server | awk '{print $1}' | xargs -r docker rm
+
+echo "✅ SFTP测试环境清理完成"
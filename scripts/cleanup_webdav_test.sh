@@ -0,0 +1,21 @@
+#!/bin/bash
+
+# WebDAV测试环境清理脚本
+
+set -e
+
+echo "正在清理WebDAV测试环境..."
+
+# 停止并删除WebDAV测试服务器
+if docker ps | grep -q webdav-test-server; then
+    echo "停止WebDAV测试服务器..."
+    docker stop webdav-test-server
+    echo "✅ WebDAV测试服务器已停止"
+else
+    echo "ℹ️  WebDAV测试服务器未在运行"
+fi
+
+# 清理可能存在的悬挂容器
+docker ps -a | grep webdav-test-server | awk '{print $1}' | xargs -r docker rm
+
+echo "✅ WebDAV测试环境清理完成"
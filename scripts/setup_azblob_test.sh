@@ -0,0 +1,55 @@
+#!/bin/bash
+
+# Azure Blob测试环境设置脚本
+# 此脚本启动Azurite模拟器并创建集成测试使用的容器
+
+set -e
+
+echo "正在设置Azure Blob测试环境..."
+
+# 检查Docker是否可用
+if ! command -v docker &> /dev/null; then
+    echo "错误: 需要安装Docker来运行Azurite模拟器"
+    exit 1
+fi
+
+AZURITE_IMAGE="mcr.microsoft.com/azure-storage/azurite"
+AZURE_CLI_IMAGE="mcr.microsoft.com/azure-cli"
+CONTAINER_NAME="testcontainer"
+CONNECTION_STRING="DefaultEndpointsProtocol=http;AccountName=devstoreaccount1;AccountKey=Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==;BlobEndpoint=http://127.0.0.1:10000/devstoreaccount1;"
+
+# 启动Azurite（只启用Blob服务）
+echo "启动Azurite模拟器 (端口 10000)..."
+docker run --rm -d \
+    --name azurite-test-server \
+    -p 10000:10000 \
+    $AZURITE_IMAGE \
+    azurite-blob --blobHost 0.0.0.0 --blobPort 10000
+
+# 等待服务器启动
+echo "等待Azurite启动..."
+sleep 3
+
+if ! docker ps | grep -q azurite-test-server; then
+    echo "❌ Azurite模拟器启动失败"
+    exit 1
+fi
+
+# 创建测试容器
+echo "创建测试容器: $CONTAINER_NAME"
+docker run --rm --network host $AZURE_CLI_IMAGE \
+    az storage container create \
+    --name "$CONTAINER_NAME" \
+    --connection-string "$CONNECTION_STRING" \
+    --output none
+
+echo "✅ Azurite模拟器已启动"
+echo "   端点: http://127.0.0.1:10000/devstoreaccount1"
+echo "   容器: $CONTAINER_NAME"
+echo "   连接字符串: UseDevelopmentStorage=true"
+echo ""
+echo "现在可以运行Azure Blob集成测试:"
+echo "cargo test --test azblob_integration_tests"
+echo ""
+echo "要停止模拟器，请运行:"
+echo "docker stop azurite-test-server"
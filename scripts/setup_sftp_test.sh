@@ -0,0 +1,57 @@
+#!/bin/bash
+
+# SFTP测试环境设置脚本
+# 此脚本用于设置SFTP服务器用于集成测试
+
+set -e
+
+echo "正在设置SFTP测试环境..."
+
+# 检查Docker是否可用
+if ! command -v docker &> /dev/null; then
+    echo "错误: 需要安装Docker来运行SFTP测试服务器"
+    exit 1
+fi
+
+SFTP_IMAGE="atmoz/sftp"
+SCRIPT_DIR=$(cd "$(dirname "$0")" && pwd)
+KEY_DIR="$SCRIPT_DIR/../target/sftp-test"
+
+# 生成私钥认证测试使用的密钥
+mkdir -p "$KEY_DIR"
+if [ ! -f "$KEY_DIR/id_ed25519" ]; then
+    echo "生成测试密钥: $KEY_DIR/id_ed25519"
+    ssh-keygen -q -t ed25519 -N "" -f "$KEY_DIR/id_ed25519"
+fi
+
+# 启动SFTP测试服务器
+echo "启动SFTP测试服务器 (端口 2222)..."
+docker run --rm -d \
+    --name sftp-test-server \
+    -p 2222:22 \
+    -v "$KEY_DIR/id_ed25519.pub:/home/testuser/.ssh/keys/id_ed25519.pub:ro" \
+    $SFTP_IMAGE \
+    testuser:testpass:::upload
+
+# 等待服务器启动
+echo "等待SFTP服务器启动..."
+sleep 3
+
+# 检查服务器是否正在运行
+if docker ps | grep -q sftp-test-server; then
+    echo "✅ SFTP测试服务器已启动"
+    echo "   主机: 127.0.0.1"
+    echo "   端口: 2222"
+    echo "   用户名: testuser"
+    echo "   密码: testpass"
+    echo "   私钥: $KEY_DIR/id_ed25519"
+    echo ""
+    echo "现在可以运行SFTP集成测试:"
+    echo "cargo test --test sftp_integration_tests"
+    echo ""
+    echo "要停止测试服务器，请运行:"
+    echo "docker stop sftp-test-server"
+else
+    echo "❌ SFTP测试服务器启动失败"
+    exit 1
+fi
@@ -0,0 +1,47 @@
+#!/bin/bash
+
+# WebDAV测试环境设置脚本
+# 此脚本用于设置WebDAV服务器用于集成测试
+
+set -e
+
+echo "正在设置WebDAV测试环境..."
+
+# 检查Docker是否可用
+if ! command -v docker &> /dev/null; then
+    echo "错误: 需要安装Docker来运行WebDAV测试服务器"
+    exit 1
+fi
+
+WEBDAV_IMAGE="bytemark/webdav"
+
+# 启动WebDAV测试服务器
+echo "启动WebDAV测试服务器 (端口 8080)..."
+docker run --rm -d \
+    --name webdav-test-server \
+    -p 8080:80 \
+    -e AUTH_TYPE=Basic \
+    -e USERNAME=testuser \
+    -e PASSWORD=testpass \
+    $WEBDAV_IMAGE
+
+# 等待服务器启动
+echo "等待WebDAV服务器启动..."
+sleep 3
+
+# 检查服务器是否正在运行
+if docker ps | grep -q webdav-test-server; then
+    echo "✅ WebDAV测试服务器已启动"
+    echo "   地址: http://127.0.0.1:8080"
+    echo "   用户名: testuser"
+    echo "   密码: testpass"
+    echo ""
+    echo "现在可以运行WebDAV集成测试:"
+    echo "cargo test --test webdav_integration_tests"
+    echo ""
+    echo "要停止测试服务器，请运行:"
+    echo "docker stop webdav-test-server"
+else
+    echo "❌ WebDAV测试服务器启动失败"
+    exit 1
+fi
@@ -1,19 +1,30 @@
 use std::collections::HashMap;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use clap::{Arg, ArgAction, ArgMatches, Command};
+use indicatif::{ProgressBar, ProgressStyle};
 use log::debug;
 use serde_json::Value;
 
 use crate::core::operator;
-use crate::core::{ConnectionConfig, ConnectionManager, Error, Result};
+use crate::core::sync::{self, SyncAction, SyncPlan};
+use crate::core::throttle::RateLimiter;
+use crate::core::transfer::{TransferReport, DEFAULT_TRANSFER_CONCURRENCY};
+use crate::core::{
+    ConnectionConfig, ConnectionManager, Error, FileManager, Result, TransferProgress,
+};
+use crate::protocols::fs::FSProtocol;
+use crate::protocols::s3::client::{BucketInfo, BucketStatus, S3Client};
+use crate::protocols::s3::multipart::{MultipartOptions, MultipartSession, MultipartUploader};
+use crate::protocols::s3::{bucket_config, create_service_client, S3Protocol};
+use crate::protocols::Protocol;
 
 #[allow(dead_code)]
 pub struct App {
     conn_manager: ConnectionManager,
+    config_dir: PathBuf,
 }
 
 #[allow(dead_code)]
@@ -33,7 +44,10 @@ impl App {
 
         let conn_manager = ConnectionManager::new(config_path)?;
 
-        Ok(Self { conn_manager })
+        Ok(Self {
+            conn_manager,
+            config_dir,
+        })
     }
 
     pub async fn run(&mut self) -> Result<()> {
@@ -46,8 +60,13 @@ impl App {
             Some(("upload", sub_matches)) => self.handle_upload_command(sub_matches).await,
             Some(("download", sub_matches)) => self.handle_download_command(sub_matches).await,
             Some(("rm", sub_matches)) => self.handle_rm_command(sub_matches).await,
+            Some(("cp", sub_matches)) => self.handle_cp_command(sub_matches, false).await,
+            Some(("mv", sub_matches)) => self.handle_cp_command(sub_matches, true).await,
+            Some(("sync", sub_matches)) => self.handle_sync_command(sub_matches).await,
             Some(("mkdir", sub_matches)) => self.handle_mkdir_command(sub_matches).await,
             Some(("stat", sub_matches)) => self.handle_stat_command(sub_matches).await,
+            Some(("multipart", sub_matches)) => self.handle_multipart_command(sub_matches).await,
+            Some(("bucket", sub_matches)) => self.handle_bucket_command(sub_matches).await,
             _ => Err(Error::new_other("无效的命令")),
         }
     }
@@ -91,6 +110,19 @@ impl App {
                                     .long("config")
                                     .help("连接配置 (JSON 格式)")
                                     .required(true),
+                            )
+                            .arg(
+                                Arg::new("discover")
+                                    .long("discover")
+                                    .help("列出凭证可见的 S3 bucket，为选中的每个 bucket 添加一个连接")
+                                    .action(ArgAction::SetTrue),
+                            )
+                            .arg(
+                                Arg::new("select")
+                                    .long("select")
+                                    .value_name("BUCKETS")
+                                    .help("配合 --discover 使用，直接选择 bucket（序号或名称，逗号分隔，all 表示全部）")
+                                    .requires("discover"),
                             ),
                     )
                     .subcommand(
@@ -122,7 +154,33 @@ impl App {
                             .required(true),
                     )
                     .arg(Arg::new("local_path").help("本地文件路径").required(true))
-                    .arg(Arg::new("remote_path").help("远程文件路径").required(true)),
+                    .arg(Arg::new("remote_path").help("远程文件路径").required(true))
+                    .arg(
+                        Arg::new("recursive")
+                            .short('r')
+                            .long("recursive")
+                            .help("递归上传目录")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("jobs")
+                            .short('j')
+                            .long("jobs")
+                            .help("递归上传时并发传输的文件数")
+                            .value_parser(clap::value_parser!(usize)),
+                    )
+                    .arg(
+                        Arg::new("part_size")
+                            .long("part-size")
+                            .help("S3 分片上传的分片大小，如 16M"),
+                    )
+                    .arg(
+                        Arg::new("concurrency")
+                            .long("concurrency")
+                            .help("S3 分片上传的并发分片数")
+                            .value_parser(clap::value_parser!(usize)),
+                    )
+                    .arg(limit_rate_arg()),
             )
             .subcommand(
                 Command::new("download")
@@ -135,7 +193,28 @@ impl App {
                             .required(true),
                     )
                     .arg(Arg::new("remote_path").help("远程文件路径").required(true))
-                    .arg(Arg::new("local_path").help("本地文件路径").required(true)),
+                    .arg(Arg::new("local_path").help("本地文件路径").required(true))
+                    .arg(
+                        Arg::new("recursive")
+                            .short('r')
+                            .long("recursive")
+                            .help("递归下载目录")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("jobs")
+                            .short('j')
+                            .long("jobs")
+                            .help("递归下载时并发传输的文件数")
+                            .value_parser(clap::value_parser!(usize)),
+                    )
+                    .arg(
+                        Arg::new("continue")
+                            .long("continue")
+                            .help("断点续传，从本地已下载的部分继续")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(limit_rate_arg()),
             )
             .subcommand(
                 Command::new("rm")
@@ -156,6 +235,66 @@ impl App {
                             .action(ArgAction::SetTrue),
                     ),
             )
+            .subcommand(
+                Command::new("cp")
+                    .about("复制文件或目录（目录递归复制），支持 连接ID:/路径 形式跨连接传输")
+                    .arg(
+                        Arg::new("connection")
+                            .short('c')
+                            .long("connection")
+                            .help("连接 ID（用于没有 连接ID: 前缀的路径）"),
+                    )
+                    .arg(Arg::new("source").help("源路径").required(true))
+                    .arg(Arg::new("target").help("目标路径").required(true))
+                    .arg(limit_rate_arg()),
+            )
+            .subcommand(
+                Command::new("mv")
+                    .about("移动或重命名文件或目录，支持 连接ID:/路径 形式跨连接移动")
+                    .arg(
+                        Arg::new("connection")
+                            .short('c')
+                            .long("connection")
+                            .help("连接 ID（用于没有 连接ID: 前缀的路径）"),
+                    )
+                    .arg(Arg::new("source").help("源路径").required(true))
+                    .arg(Arg::new("target").help("目标路径").required(true))
+                    .arg(limit_rate_arg()),
+            )
+            .subcommand(
+                Command::new("sync")
+                    .about("同步两个目录（本地或连接之间），只传输有变化的文件")
+                    .arg(
+                        Arg::new("source")
+                            .help("源目录：本地路径或 连接ID:/路径")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("target")
+                            .help("目标目录：本地路径或 连接ID:/路径")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("delete")
+                            .long("delete")
+                            .help("删除目标端多余的文件")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("dry_run")
+                            .long("dry-run")
+                            .help("只打印同步计划，不执行")
+                            .action(ArgAction::SetTrue),
+                    )
+                    .arg(
+                        Arg::new("jobs")
+                            .short('j')
+                            .long("jobs")
+                            .help("并发传输的文件数")
+                            .value_parser(clap::value_parser!(usize)),
+                    )
+                    .arg(limit_rate_arg()),
+            )
             .subcommand(
                 Command::new("mkdir")
                     .about("创建目录")
@@ -180,6 +319,76 @@ impl App {
                     )
                     .arg(Arg::new("path").help("远程路径").required(true)),
             )
+            .subcommand(
+                Command::new("multipart")
+                    .about("管理 S3 未完成的分片上传")
+                    .subcommand_required(true)
+                    .subcommand(
+                        Command::new("list")
+                            .about("列出未完成的分片上传")
+                            .arg(
+                                Arg::new("connection")
+                                    .short('c')
+                                    .long("connection")
+                                    .help("连接 ID")
+                                    .required(true),
+                            )
+                            .arg(Arg::new("prefix").help("对象键前缀").default_value("")),
+                    )
+                    .subcommand(
+                        Command::new("abort")
+                            .about("中止分片上传")
+                            .arg(
+                                Arg::new("connection")
+                                    .short('c')
+                                    .long("connection")
+                                    .help("连接 ID")
+                                    .required(true),
+                            )
+                            .arg(Arg::new("key").help("对象键"))
+                            .arg(Arg::new("upload_id").help("分片上传 ID"))
+                            .arg(
+                                Arg::new("older_than")
+                                    .long("older-than")
+                                    .help("中止所有发起时间早于指定小时数的分片上传")
+                                    .value_parser(clap::value_parser!(u64))
+                                    .conflicts_with_all(["key", "upload_id"]),
+                            ),
+                    ),
+            )
+            .subcommand(
+                Command::new("bucket")
+                    .about("管理 S3 bucket（使用指定连接的端点与凭证）")
+                    .subcommand_required(true)
+                    .subcommand(
+                        Command::new("list")
+                            .about("列出凭证可见的所有 bucket")
+                            .arg(bucket_connection_arg()),
+                    )
+                    .subcommand(
+                        Command::new("create")
+                            .about("创建 bucket")
+                            .arg(bucket_connection_arg())
+                            .arg(Arg::new("bucket").help("bucket 名称").required(true))
+                            .arg(
+                                Arg::new("region")
+                                    .long("region")
+                                    .help("创建到指定区域，默认使用连接配置的区域"),
+                            ),
+                    )
+                    .subcommand(
+                        Command::new("delete")
+                            .about("删除空 bucket")
+                            .arg(bucket_connection_arg())
+                            .arg(Arg::new("bucket").help("bucket 名称").required(true)),
+                    )
+                    .subcommand(
+                        Command::new("head")
+                            .about("查询 bucket 是否存在及所在区域")
+                            .arg(bucket_connection_arg())
+                            .arg(Arg::new("bucket").help("bucket 名称").required(true)),
+                    ),
+            )
     }
 
     async fn handle_connection_command(&mut self, matches: &ArgMatches) -> Result<()> {
@@ -221,6 +430,7 @@ impl App {
                     let display_value = if key.contains("key")
                         || key.contains("password")
                         || key.contains("secret")
+                        || key.contains("token")
                     {
                         "******".to_string()
                     } else {
@@ -255,6 +465,16 @@ impl App {
                     config.insert(key.clone(), value.as_str().unwrap().to_string());
                 }
 
+                if sub_matches.get_flag("discover") {
+                    if protocol_type != "s3" {
+                        return Err(Error::new_not_supported("--discover 仅支持 S3 连接"));
+                    }
+                    let select = sub_matches.get_one::<String>("select");
+                    return self
+                        .add_discovered_s3_connections(&name, &config, select)
+                        .await;
+                }
+
                 let conn_config = ConnectionConfig::new(name, protocol_type, config);
                 self.conn_manager.add_connection(conn_config)?;
 
@@ -272,6 +492,63 @@ impl App {
         }
     }
 
+    /// 列出凭证可见的 bucket，为选中的每个 bucket 添加一个连接
+    async fn add_discovered_s3_connections(
+        &mut self,
+        name: &str,
+        config: &HashMap<String, String>,
+        select: Option<&String>,
+    ) -> Result<()> {
+        let buckets = create_service_client(config)
+            .await?
+            .discover_buckets()
+            .await?;
+        if buckets.is_empty() {
+            return Err(Error::new_not_found("当前凭证下没有可用的 bucket"));
+        }
+
+        let selection = match select {
+            Some(select) => select.clone(),
+            None => {
+                println!("{:<6} {:<40} {:<15}", "序号", "名称", "区域");
+                println!("{:-<6} {:-<40} {:-<15}", "", "", "");
+                for (index, bucket) in buckets.iter().enumerate() {
+                    println!(
+                        "{:<6} {:<40} {:<15}",
+                        index + 1,
+                        bucket.name,
+                        bucket.region.as_deref().unwrap_or("-")
+                    );
+                }
+
+                print!("选择要添加的 bucket（序号或名称，逗号分隔，all 表示全部）: ");
+                std::io::stdout().flush()?;
+                let mut input = String::new();
+                std::io::stdin().read_line(&mut input)?;
+                input
+            }
+        };
+
+        let chosen = select_buckets(&buckets, &selection)?;
+        for bucket in &chosen {
+            // 选中多个 bucket 时用 bucket 名区分连接
+            let conn_name = if chosen.len() == 1 {
+                name.to_string()
+            } else {
+                format!("{} ({})", name, bucket.name)
+            };
+            let conn_config = ConnectionConfig::new(
+                conn_name.clone(),
+                "s3".to_string(),
+                bucket_config(config, bucket),
+            );
+            self.conn_manager.add_connection(conn_config)?;
+            println!("已添加连接: {} -> {}", conn_name, bucket.name);
+        }
+
+        Ok(())
+    }
+
     async fn handle_ls_command(&self, matches: &ArgMatches) -> Result<()> {
         let connection_id = matches.get_one::<String>("connection").unwrap();
         let path = matches.get_one::<String>("path").unwrap();
@@ -328,7 +605,8 @@ impl App {
         println!("准备上传: {} -> {}", local_path, remote_path);
 
         let protocol = self.conn_manager.create_protocol(connection_id)?;
-        let file_manager = operator::create_file_manager(protocol.as_ref())?;
+        let file_manager =
+            self.create_file_manager(protocol.as_ref(), rate_limiter(matches)?.as_ref())?;
 
         let local_path = Path::new(local_path);
 
@@ -339,15 +617,81 @@ impl App {
             )));
         }
 
-        // Simple upload without any progress tracking
-        let result = file_manager.upload(local_path, remote_path).await;
+        if local_path.is_dir() {
+            if !matches.get_flag("recursive") {
+                return Err(Error::new_other(&format!(
+                    "{} 是目录，请使用 -r 递归上传",
+                    local_path.display()
+                )));
+            }
+            let jobs = matches
+                .get_one::<usize>("jobs")
+                .copied()
+                .unwrap_or(DEFAULT_TRANSFER_CONCURRENCY);
+            let bar = create_batch_bar();
+            let report = file_manager
+                .upload_dir(local_path, remote_path, jobs, |progress| {
+                    bar.set_length(progress.total as u64);
+                    bar.set_position(progress.completed as u64);
+                    bar.set_message(progress.result.target.clone());
+                })
+                .await?;
+            bar.finish_and_clear();
+            return print_transfer_report(&report, "上传");
+        }
+
+        let size = std::fs::metadata(local_path)?.len();
+        println!("文件大小: {}", crate::utils::format::format_size(size));
+
+        // S3 大文件使用可续传的分片上传
+        let conn = self
+            .conn_manager
+            .get_connection(connection_id)
+            .ok_or_else(|| Error::new_not_found(&format!("未找到连接: {}", connection_id)))?;
+        let multipart = if conn.protocol_type == "s3" {
+            let mut options = MultipartOptions::from_config(&conn.config)?;
+            if let Some(part_size) = matches.get_one::<String>("part_size") {
+                let part_size = crate::utils::format::parse_size(part_size)
+                    .ok_or_else(|| Error::new_config(&format!("无效的分片大小: {}", part_size)))?;
+                options = options.with_part_size(part_size as usize);
+            }
+            if let Some(concurrency) = matches.get_one::<usize>("concurrency") {
+                options = options.with_concurrency(*concurrency);
+            }
+            (size > options.effective_part_size(size) as u64).then_some(options)
+        } else {
+            None
+        };
+
+        let bar = create_transfer_bar(size);
+        let on_progress = |progress: &TransferProgress| bar.set_position(progress.transferred);
+        let result = match multipart {
+            Some(options) => {
+                let client = S3Protocol::from_config(&conn.config)?
+                    .create_client()
+                    .await?;
+                MultipartUploader::new(&client, options, &self.multipart_state_dir())
+                    .with_throttle(file_manager.throttle().clone())
+                    .upload(local_path, remote_path, on_progress)
+                    .await
+            }
+            None => {
+                file_manager
+                    .upload_with_progress(local_path, remote_path, on_progress)
+                    .await
+            }
+        };
 
         match result {
             Ok(_) => {
+                bar.finish();
                 println!("文件上传成功");
                 Ok(())
             }
-            Err(e) => Err(e),
+            Err(e) => {
+                bar.abandon();
+                Err(e)
+            }
         }
     }
 
@@ -355,44 +699,68 @@ impl App {
         let connection_id = matches.get_one::<String>("connection").unwrap();
         let remote_path = matches.get_one::<String>("remote_path").unwrap();
         let local_path = matches.get_one::<String>("local_path").unwrap();
+        let resume = matches.get_flag("continue");
 
         let protocol = self.conn_manager.create_protocol(connection_id)?;
-        let file_manager = operator::create_file_manager(protocol.as_ref())?;
+        let file_manager =
+            self.create_file_manager(protocol.as_ref(), rate_limiter(matches)?.as_ref())?;
+
+        let local_path = PathBuf::from(local_path);
+
+        if matches.get_flag("recursive") {
+            println!("正在递归下载: {} -> {}", remote_path, local_path.display());
+            let jobs = matches
+                .get_one::<usize>("jobs")
+                .copied()
+                .unwrap_or(DEFAULT_TRANSFER_CONCURRENCY);
+            let bar = create_batch_bar();
+            let report = file_manager
+                .download_dir(remote_path, &local_path, jobs, |progress| {
+                    bar.set_length(progress.total as u64);
+                    bar.set_position(progress.completed as u64);
+                    bar.set_message(progress.result.source.clone());
+                })
+                .await?;
+            bar.finish_and_clear();
+            return print_transfer_report(&report, "下载");
+        }
 
         // 获取远程文件元信息
         let meta = file_manager.stat(remote_path).await?;
+        if meta.is_dir() {
+            return Err(Error::new_other(&format!(
+                "{} 是目录，请使用 -r 递归下载",
+                remote_path
+            )));
+        }
         let size = meta.content_length();
 
-        let local_path = PathBuf::from(local_path);
-
-        if size > 5 * 1024 * 1024 {
-            // 大于 5MB 的文件显示进度
-            println!("正在下载: {} -> {}", remote_path, local_path.display());
-            println!("文件大小: {}", crate::utils::format::format_size(size));
-
-            // 使用 AtomicU64 来存储进度百分比, 这样可以在闭包中安全修改
-            let progress = Arc::new(AtomicU64::new(0));
-            let progress_clone = Arc::clone(&progress);
+        println!("正在下载: {} -> {}", remote_path, local_path.display());
+        println!("文件大小: {}", crate::utils::format::format_size(size));
 
+        let bar = create_transfer_bar(size);
+        let on_progress = |progress: &TransferProgress| bar.set_position(progress.transferred);
+        let result = if resume {
             file_manager
-                .download_with_progress(remote_path, &local_path, move |current, total| {
-                    let percent = ((current as f64 / total as f64) * 100.0) as u64;
-                    let last_percent = progress_clone.load(Ordering::Relaxed);
-
-                    if percent > last_percent && percent % 10 == 0 {
-                        println!("下载进度: {}%", percent);
-                        progress_clone.store(percent, Ordering::Relaxed);
-                    }
-                })
-                .await?;
-
-            println!("下载完成: 100%");
+                .resume_download_with_progress(remote_path, &local_path, on_progress)
+                .await
         } else {
-            file_manager.download(remote_path, &local_path).await?;
-            println!("文件下载成功");
-        }
+            file_manager
+                .download_with_progress(remote_path, &local_path, on_progress)
+                .await
+        };
 
-        Ok(())
+        match result {
+            Ok(_) => {
+                bar.finish();
+                println!("文件下载成功");
+                Ok(())
+            }
+            Err(e) => {
+                bar.abandon();
+                Err(e)
+            }
+        }
     }
 
     async fn handle_rm_command(&self, matches: &ArgMatches) -> Result<()> {
@@ -403,33 +771,417 @@ impl App {
         let protocol = self.conn_manager.create_protocol(connection_id)?;
         let file_manager = operator::create_file_manager(protocol.as_ref())?;
 
-        // 获取文件/目录信息
-        let meta = file_manager.stat(path).await?;
+        let removed = remove_path(&file_manager, path, recursive, || {
+            // 确认删除提示
+            print!("确认删除 {}? [y/N] ", path);
+            std::io::stdout().flush().unwrap();
+
+            let mut input = String::new();
+            std::io::stdin().read_line(&mut input).unwrap();
+            input.trim().eq_ignore_ascii_case("y")
+        })
+        .await?;
 
-        if meta.mode().is_dir() && !recursive {
-            return Err(Error::new_other(
-                "无法删除目录，请使用 --recursive 参数递归删除",
-            ));
+        if removed {
+            println!("删除成功");
+        } else {
+            println!("操作取消");
         }
+        Ok(())
+    }
 
-        // 确认删除提示
-        print!("确认删除 {}? [y/N] ", path);
-        std::io::stdout().flush().unwrap();
+    async fn handle_cp_command(&self, matches: &ArgMatches, remove_source: bool) -> Result<()> {
+        let source = matches.get_one::<String>("source").unwrap();
+        let target = matches.get_one::<String>("target").unwrap();
+        let limiter = rate_limiter(matches)?;
+
+        // 带有 连接ID: 前缀的一端使用前缀中的连接，另一端使用 -c 指定的连接
+        let connection = matches.get_one::<String>("connection").map(String::as_str);
+        let (source_conn, source_path) = resolve_remote_spec(source, connection)?;
+        let (target_conn, target_path) = resolve_remote_spec(target, connection)?;
+
+        // 源和目标位于不同连接时，在两个连接之间直接传输
+        if source_conn != target_conn {
+            return self
+                .handle_transfer(
+                    (source_conn, source_path),
+                    (target_conn, target_path),
+                    limiter.as_ref(),
+                    remove_source,
+                )
+                .await;
+        }
 
-        let mut input = String::new();
-        std::io::stdin().read_line(&mut input).unwrap();
+        let protocol = self.conn_manager.create_protocol(source_conn)?;
+        let file_manager = self.create_file_manager(protocol.as_ref(), limiter.as_ref())?;
 
-        if !input.trim().eq_ignore_ascii_case("y") {
-            println!("操作取消");
+        if remove_source {
+            file_manager.rename(source_path, target_path).await?;
+            println!("移动成功: {} -> {}", source, target);
+        } else {
+            file_manager.copy(source_path, target_path).await?;
+            println!("复制成功: {} -> {}", source, target);
+        }
+
+        Ok(())
+    }
+
+    /// 在两个连接之间流式传输文件或目录，移动时全部成功后删除源
+    async fn handle_transfer(
+        &self,
+        source: (&str, &str),
+        target: (&str, &str),
+        rate_limiter: Option<&Arc<RateLimiter>>,
+        remove_source: bool,
+    ) -> Result<()> {
+        let (source_conn, source_path) = source;
+        let (target_conn, target_path) = target;
+        println!(
+            "正在传输: {}:{} -> {}:{}",
+            source_conn, source_path, target_conn, target_path
+        );
+
+        let source_manager = self.create_file_manager(
+            self.conn_manager.create_protocol(source_conn)?.as_ref(),
+            rate_limiter,
+        )?;
+        let target_manager = self.create_file_manager(
+            self.conn_manager.create_protocol(target_conn)?.as_ref(),
+            rate_limiter,
+        )?;
+
+        let bar = create_transfer_bar(0);
+        let on_progress = |progress: &TransferProgress| {
+            bar.set_length(progress.total);
+            bar.set_position(progress.transferred);
+        };
+        let report = if remove_source {
+            source_manager
+                .move_to(source_path, &target_manager, target_path, on_progress)
+                .await?
+        } else {
+            source_manager
+                .transfer_to(source_path, &target_manager, target_path, on_progress)
+                .await?
+        };
+        bar.finish_and_clear();
+
+        print_transfer_report(&report, if remove_source { "移动" } else { "传输" })
+    }
+
+    async fn handle_sync_command(&self, matches: &ArgMatches) -> Result<()> {
+        let source_spec = matches.get_one::<String>("source").unwrap();
+        let target_spec = matches.get_one::<String>("target").unwrap();
+        let delete = matches.get_flag("delete");
+        let dry_run = matches.get_flag("dry_run");
+
+        let jobs = matches
+            .get_one::<usize>("jobs")
+            .copied()
+            .unwrap_or(DEFAULT_TRANSFER_CONCURRENCY);
+
+        // 源和目标共享同一个全局限制器，合计带宽不超过 --limit-rate
+        let limiter = rate_limiter(matches)?;
+        let (source, source_root) =
+            self.resolve_sync_endpoint(source_spec, false, limiter.as_ref())?;
+        let (target, target_root) =
+            self.resolve_sync_endpoint(target_spec, true, limiter.as_ref())?;
+
+        println!("正在比较: {} -> {}", source_spec, target_spec);
+        let plan = SyncPlan::build(
+            &sync::scan(&source, &source_root).await?,
+            &sync::scan(&target, &target_root).await?,
+            delete,
+        );
+
+        if plan.is_empty() {
+            println!("目标已是最新，无需同步");
             return Ok(());
         }
 
-        file_manager.delete(path).await?;
-        println!("删除成功");
+        for item in &plan.items {
+            let action = match item.action {
+                SyncAction::Create => "新建",
+                SyncAction::Update => "更新",
+                SyncAction::Delete => "删除",
+            };
+            println!(
+                "{:<6} {:<50} {}",
+                action,
+                item.path,
+                crate::utils::format::format_size(item.size)
+            );
+        }
+        println!(
+            "\n共 {} 项，需传输 {}",
+            plan.items.len(),
+            crate::utils::format::format_size(plan.transfer_bytes())
+        );
+
+        if dry_run {
+            println!("(dry-run 模式，未执行任何操作)");
+            return Ok(());
+        }
 
+        // 多个文件并发传输时按文件记录已传输字节，进度条显示整个同步计划的合计进度
+        let bar = create_transfer_bar(plan.transfer_bytes());
+        let mut transferred: HashMap<String, u64> = HashMap::new();
+        let report = sync::execute(
+            &source,
+            &source_root,
+            &target,
+            &target_root,
+            &plan,
+            jobs,
+            |progress| {
+                let previous = transferred
+                    .insert(progress.path.clone(), progress.transferred)
+                    .unwrap_or(0);
+                bar.set_position((bar.position() + progress.transferred).saturating_sub(previous));
+            },
+        )
+        .await;
+        bar.finish_and_clear();
+
+        for result in report.failed() {
+            println!(
+                "失败   {:<50} {}",
+                result.item.path,
+                result.error.as_deref().unwrap_or_default()
+            );
+        }
+        let summary = report.summary();
+        println!(
+            "同步完成: 新建 {} 个，更新 {} 个，删除 {} 个，失败 {} 个，共传输 {}",
+            summary.created,
+            summary.updated,
+            summary.deleted,
+            summary.failed,
+            crate::utils::format::format_size(summary.bytes_transferred)
+        );
+
+        if summary.failed > 0 {
+            return Err(Error::new_other(&format!("{} 项同步失败", summary.failed)));
+        }
         Ok(())
     }
 
+    /// 解析同步端点：`连接ID:/路径` 使用已保存的连接，其余视为本地目录
+    ///
+    /// 返回文件管理器与同步根路径。
+    fn resolve_sync_endpoint(
+        &self,
+        spec: &str,
+        create_local: bool,
+        rate_limiter: Option<&Arc<RateLimiter>>,
+    ) -> Result<(FileManager, String)> {
+        if let Some((connection_id, path)) = parse_remote_spec(spec) {
+            if !Path::new(spec).exists() {
+                let protocol = self.conn_manager.create_protocol(connection_id)?;
+                let file_manager = self.create_file_manager(protocol.as_ref(), rate_limiter)?;
+                return Ok((file_manager, path.to_string()));
+            }
+        }
+
+        let local_dir = Path::new(spec);
+        if create_local {
+            std::fs::create_dir_all(local_dir)?;
+        } else if !local_dir.is_dir() {
+            return Err(Error::new_not_found(&format!("本地目录不存在: {}", spec)));
+        }
+        let file_manager = self.create_file_manager(&FSProtocol::new(local_dir), rate_limiter)?;
+        Ok((file_manager, "/".to_string()))
+    }
+
+    /// 创建文件管理器，在连接自身的限速之外叠加命令行指定的全局限速
+    fn create_file_manager(
+        &self,
+        protocol: &dyn Protocol,
+        rate_limiter: Option<&Arc<RateLimiter>>,
+    ) -> Result<FileManager> {
+        let file_manager = operator::create_file_manager(protocol)?;
+        Ok(match rate_limiter {
+            Some(limiter) => file_manager.with_rate_limiter(limiter.clone()),
+            None => file_manager,
+        })
+    }
+
+    async fn handle_multipart_command(&self, matches: &ArgMatches) -> Result<()> {
+        match matches.subcommand() {
+            Some(("list", sub_matches)) => {
+                let connection_id = sub_matches.get_one::<String>("connection").unwrap();
+                let prefix = sub_matches.get_one::<String>("prefix").unwrap();
+
+                let client = self.create_s3_client(connection_id).await?;
+                let uploads = client.list_multipart_uploads(prefix).await?;
+                if uploads.is_empty() {
+                    println!("没有未完成的分片上传");
+                    return Ok(());
+                }
+
+                let sessions = MultipartSession::list(&self.multipart_state_dir());
+                println!(
+                    "{:<40} {:<25} {:<8} {:<20}",
+                    "对象键", "发起时间", "可续传", "上传 ID"
+                );
+                println!("{:-<40} {:-<25} {:-<8} {:-<20}", "", "", "", "");
+                for upload in uploads {
+                    let resumable = sessions
+                        .iter()
+                        .any(|(_, session)| session.upload_id == upload.upload_id);
+                    println!(
+                        "{:<40} {:<25} {:<8} {}",
+                        upload.key,
+                        upload.initiated,
+                        if resumable { "是" } else { "否" },
+                        upload.upload_id
+                    );
+                }
+
+                Ok(())
+            }
+            Some(("abort", sub_matches)) => {
+                let connection_id = sub_matches.get_one::<String>("connection").unwrap();
+                let client = self.create_s3_client(connection_id).await?;
+                let uploader = MultipartUploader::new(
+                    &client,
+                    MultipartOptions::default(),
+                    &self.multipart_state_dir(),
+                );
+
+                if let Some(hours) = sub_matches.get_one::<u64>("older_than") {
+                    let cutoff = chrono::Utc::now() - chrono::Duration::hours(*hours as i64);
+                    let mut aborted = 0;
+                    for upload in client.list_multipart_uploads("").await? {
+                        let initiated = chrono::DateTime::parse_from_rfc3339(&upload.initiated)
+                            .map_err(|e| {
+                                Error::new_protocol(&format!(
+                                    "无法解析发起时间 {}: {}",
+                                    upload.initiated, e
+                                ))
+                            })?;
+                        if initiated < cutoff {
+                            uploader.abort(&upload.key, &upload.upload_id).await?;
+                            println!("已中止: {} ({})", upload.key, upload.upload_id);
+                            aborted += 1;
+                        }
+                    }
+                    println!("共中止 {} 个分片上传", aborted);
+                    return Ok(());
+                }
+
+                let (Some(key), Some(upload_id)) = (
+                    sub_matches.get_one::<String>("key"),
+                    sub_matches.get_one::<String>("upload_id"),
+                ) else {
+                    return Err(Error::new_config(
+                        "请指定对象键和上传 ID，或使用 --older-than",
+                    ));
+                };
+
+                uploader.abort(key, upload_id).await?;
+                println!("分片上传已中止");
+                Ok(())
+            }
+            _ => Err(Error::new_other("无效的分片上传命令")),
+        }
+    }
+
+    async fn handle_bucket_command(&self, matches: &ArgMatches) -> Result<()> {
+        let Some((command, sub_matches)) = matches.subcommand() else {
+            return Err(Error::new_other("无效的 bucket 命令"));
+        };
+        let connection_id = sub_matches.get_one::<String>("connection").unwrap();
+        let conn = self
+            .conn_manager
+            .get_connection(connection_id)
+            .ok_or_else(|| Error::new_not_found(&format!("未找到连接: {}", connection_id)))?;
+        if conn.protocol_type != "s3" {
+            return Err(Error::new_not_supported("bucket 管理仅支持 S3 连接"));
+        }
+
+        let mut config = conn.config.clone();
+        // 只有 create 定义了 --region
+        if let Ok(Some(region)) = sub_matches.try_get_one::<String>("region") {
+            config.insert("region".to_string(), region.clone());
+        }
+        let client = create_service_client(&config).await?;
+
+        match command {
+            "list" => {
+                let buckets = client.list_buckets().await?;
+                if buckets.is_empty() {
+                    println!("没有找到任何 bucket");
+                    return Ok(());
+                }
+
+                println!("{:<40} {:<15} {:<25}", "名称", "区域", "创建时间");
+                println!("{:-<40} {:-<15} {:-<25}", "", "", "");
+                for bucket in buckets {
+                    println!(
+                        "{:<40} {:<15} {:<25}",
+                        bucket.name,
+                        bucket.region.as_deref().unwrap_or("-"),
+                        bucket.creation_date.as_deref().unwrap_or("-")
+                    );
+                }
+                Ok(())
+            }
+            "create" => {
+                let bucket = sub_matches.get_one::<String>("bucket").unwrap();
+                client.create_bucket(bucket).await?;
+                println!("已在区域 {} 创建 bucket: {}", client.region(), bucket);
+                Ok(())
+            }
+            "delete" => {
+                let bucket = sub_matches.get_one::<String>("bucket").unwrap();
+                client.delete_bucket(bucket).await?;
+                println!("已删除 bucket: {}", bucket);
+                Ok(())
+            }
+            "head" => {
+                let bucket = sub_matches.get_one::<String>("bucket").unwrap();
+                let head = client.head_bucket(bucket).await?;
+                let status = match head.status {
+                    BucketStatus::Accessible => "存在，可以访问",
+                    BucketStatus::WrongRegion => "存在，但位于其他区域",
+                    BucketStatus::Forbidden => "存在，但无权访问",
+                    BucketStatus::NotFound => "不存在",
+                };
+                println!("bucket: {}", bucket);
+                println!("状态: {}", status);
+                if let Some(region) = head.region {
+                    println!("区域: {}", region);
+                }
+                if head.status.exists() {
+                    Ok(())
+                } else {
+                    Err(Error::new_not_found(&format!("bucket 不存在: {}", bucket)))
+                }
+            }
+            _ => Err(Error::new_other("无效的 bucket 命令")),
+        }
+    }
+
+    /// 本地保存分片上传会话的目录
+    fn multipart_state_dir(&self) -> PathBuf {
+        self.config_dir.join("multipart")
+    }
+
+    /// 为 S3 连接创建 REST API 客户端
+    async fn create_s3_client(&self, connection_id: &str) -> Result<S3Client> {
+        let conn = self
+            .conn_manager
+            .get_connection(connection_id)
+            .ok_or_else(|| Error::new_not_found(&format!("未找到连接: {}", connection_id)))?;
+
+        if conn.protocol_type != "s3" {
+            return Err(Error::new_not_supported("分片上传管理仅支持 S3 连接"));
+        }
+
+        S3Protocol::from_config(&conn.config)?.create_client().await
+    }
+
     async fn handle_mkdir_command(&self, matches: &ArgMatches) -> Result<()> {
         let connection_id = matches.get_one::<String>("connection").unwrap();
         let path = matches.get_one::<String>("path").unwrap();
@@ -493,3 +1245,251 @@ impl App {
         Ok(())
     }
 }
+
+/// 解析 `连接ID:/路径` 形式的远程路径
+///
+/// `C:\data` 这样的 Windows 盘符路径不视为远程路径。
+fn parse_remote_spec(spec: &str) -> Option<(&str, &str)> {
+    let (connection, path) = spec.split_once(':')?;
+    if connection.is_empty() || connection.contains(['/', '\\']) {
+        return None;
+    }
+    let is_drive = connection.len() == 1
+        && connection.chars().all(|c| c.is_ascii_alphabetic())
+        && path.starts_with(['/', '\\']);
+    if is_drive {
+        return None;
+    }
+    Some((connection, if path.is_empty() { "/" } else { path }))
+}
+
+/// 解析 cp/mv 的路径：带 `连接ID:` 前缀时使用前缀中的连接，否则使用 `-c` 指定的连接
+fn resolve_remote_spec<'a>(
+    spec: &'a str,
+    connection: Option<&'a str>,
+) -> Result<(&'a str, &'a str)> {
+    match (parse_remote_spec(spec), connection) {
+        (Some(remote), _) => Ok(remote),
+        (None, Some(connection)) => Ok((connection, spec)),
+        (None, None) => Err(Error::new_config(&format!(
+            "无法确定 {} 所在的连接：请使用 -c 指定连接，或使用 连接ID:/路径 格式",
+            spec
+        ))),
+    }
+}
+
+/// 解析 bucket 选择：序号（从 1 开始）或名称，逗号分隔，`all` 表示全部
+fn select_buckets<'a>(buckets: &'a [BucketInfo], selection: &str) -> Result<Vec<&'a BucketInfo>> {
+    let selection = selection.trim();
+    if selection.eq_ignore_ascii_case("all") {
+        return Ok(buckets.iter().collect());
+    }
+
+    let mut chosen: Vec<&BucketInfo> = Vec::new();
+    for item in selection
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        let bucket = match item.parse::<usize>() {
+            Ok(index) if index >= 1 => buckets.get(index - 1),
+            _ => buckets.iter().find(|b| b.name == item),
+        }
+        .ok_or_else(|| Error::new_config(&format!("无效的 bucket 选择: {}", item)))?;
+        if !chosen.iter().any(|b| b.name == bucket.name) {
+            chosen.push(bucket);
+        }
+    }
+
+    if chosen.is_empty() {
+        return Err(Error::new_config("没有选择任何 bucket"));
+    }
+    Ok(chosen)
+}
+
+/// bucket 命令共用的连接参数，使用该连接的端点与凭证
+fn bucket_connection_arg() -> Arg {
+    Arg::new("connection")
+        .short('c')
+        .long("connection")
+        .help("S3 连接 ID")
+        .required(true)
+}
+
+/// 传输命令共用的 --limit-rate 参数
+fn limit_rate_arg() -> Arg {
+    Arg::new("limit_rate")
+        .long("limit-rate")
+        .value_name("RATE")
+        .help("限制传输带宽（每秒字节数），如 512K、5M")
+}
+
+/// 解析 --limit-rate 参数，本次命令中的所有传输共享同一个限制器
+fn rate_limiter(matches: &ArgMatches) -> Result<Option<Arc<RateLimiter>>> {
+    let Some(value) = matches.get_one::<String>("limit_rate") else {
+        return Ok(None);
+    };
+    match crate::utils::format::parse_size(value) {
+        Some(0) => Ok(None),
+        Some(rate) => Ok(Some(Arc::new(RateLimiter::new(rate)))),
+        None => Err(Error::new_config(&format!("无效的限速值: {}", value))),
+    }
+}
+
+/// 创建按文件数计数的批量传输进度条
+fn create_batch_bar() -> ProgressBar {
+    let bar = ProgressBar::new(0);
+    bar.set_style(
+        ProgressStyle::with_template("[{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            .unwrap()
+            .progress_chars("#>-"),
+    );
+    bar
+}
+
+/// 打印批量传输报告，存在失败文件时返回错误
+fn print_transfer_report(report: &TransferReport, action: &str) -> Result<()> {
+    println!("{:<6} {:<50} {:<12} 错误", "状态", "文件", "大小");
+    for result in &report.results {
+        println!(
+            "{:<6} {:<50} {:<12} {}",
+            if result.is_success() {
+                "成功"
+            } else {
+                "失败"
+            },
+            result.target,
+            crate::utils::format::format_size(result.size),
+            result.error.as_deref().unwrap_or("-")
+        );
+    }
+
+    let failed = report.failed().count();
+    println!(
+        "\n{}完成: 成功 {} 个，失败 {} 个，共 {}",
+        action,
+        report.succeeded().count(),
+        failed,
+        crate::utils::format::format_size(report.transferred_bytes())
+    );
+
+    if failed > 0 {
+        return Err(Error::new_other(&format!(
+            "{} 个文件{}失败",
+            failed, action
+        )));
+    }
+    Ok(())
+}
+
+/// 创建传输进度条，显示已传输字节数、速率与剩余时间
+/// 删除文件或目录，目录需要指定 recursive；确认回调返回 false 时不删除并返回 false
+///
+/// 对象存储中只由对象前缀构成的目录不能直接 stat，通过 [`FileManager::is_dir_path`] 识别。
+async fn remove_path(
+    file_manager: &FileManager,
+    path: &str,
+    recursive: bool,
+    confirm: impl FnOnce() -> bool,
+) -> Result<bool> {
+    let is_dir = file_manager.is_dir_path(path).await?;
+    if is_dir && !recursive {
+        return Err(Error::new_other(
+            "无法删除目录，请使用 --recursive 参数递归删除",
+        ));
+    }
+
+    if !confirm() {
+        return Ok(false);
+    }
+
+    if is_dir {
+        file_manager.delete_recursive(path).await?;
+    } else {
+        file_manager.delete(path).await?;
+    }
+    Ok(true)
+}
+
+fn create_transfer_bar(total: u64) -> ProgressBar {
+    let bar = ProgressBar::new(total);
+    bar.set_style(
+        ProgressStyle::with_template(
+            "[{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({binary_bytes_per_sec}, 剩余 {eta})",
+        )
+        .unwrap()
+        .progress_chars("#>-"),
+    );
+    bar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opendal::{services, Operator};
+
+    /// 内存服务与 S3 一样没有真正的目录，目录只由对象前缀构成
+    async fn create_object_store(paths: &[&str]) -> FileManager {
+        let operator = Operator::new(services::Memory::default()).unwrap().finish();
+        for path in paths {
+            operator
+                .write(path, path.as_bytes().to_vec())
+                .await
+                .unwrap();
+        }
+        FileManager::new(operator)
+    }
+
+    #[tokio::test]
+    async fn test_remove_prefix_only_directory() {
+        let file_manager = create_object_store(&["photos/a.jpg", "photos/2024/b.jpg"]).await;
+        assert!(file_manager.stat("photos").await.is_err());
+
+        // 不带 -r 时拒绝删除目录
+        assert!(remove_path(&file_manager, "photos", false, || true)
+            .await
+            .is_err());
+
+        assert!(remove_path(&file_manager, "photos", true, || true)
+            .await
+            .unwrap());
+        assert!(file_manager.stat("photos/a.jpg").await.is_err());
+        assert!(file_manager.stat("photos/2024/b.jpg").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_remove_path_cancelled() {
+        let file_manager = create_object_store(&["a.txt"]).await;
+
+        assert!(!remove_path(&file_manager, "a.txt", false, || false)
+            .await
+            .unwrap());
+        assert!(file_manager.stat("a.txt").await.is_ok());
+    }
+
+    #[test]
+    fn test_mv_accepts_limit_rate() {
+        let app = App {
+            conn_manager: ConnectionManager::empty(PathBuf::from("connections.json")),
+            config_dir: PathBuf::new(),
+        };
+        let matches = app
+            .build_cli()
+            .try_get_matches_from(["mpfm", "mv", "a:/x", "b:/x", "--limit-rate", "1M"])
+            .unwrap();
+        let (_, mv) = matches.subcommand().unwrap();
+        assert!(rate_limiter(mv).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_parse_remote_spec() {
+        assert_eq!(parse_remote_spec("s3:/photos"), Some(("s3", "/photos")));
+        assert_eq!(parse_remote_spec("s3:"), Some(("s3", "/")));
+        assert_eq!(parse_remote_spec("/tmp/a:b"), None);
+        assert_eq!(parse_remote_spec("a.txt"), None);
+
+        // Windows 盘符路径
+        assert_eq!(parse_remote_spec("C:\\data\\a.txt"), None);
+        assert_eq!(parse_remote_spec("d:/data/a.txt"), None);
+    }
+}
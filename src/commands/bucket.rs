@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use tauri::command;
+
+use crate::core::error::{Error, ErrorKind, Result};
+use crate::protocols::s3::client::{BucketHead, BucketInfo, BucketStatus, S3Client};
+use crate::protocols::s3::{create_service_client, S3Protocol};
+
+use super::types::ApiResponse;
+
+/// 按连接配置创建绑定 bucket 的客户端，`config` 与保存连接时的配置相同
+async fn bucket_client(config: &HashMap<String, String>) -> Result<S3Client> {
+    S3Protocol::from_config(config)?.create_client().await
+}
+
+/// 添加连接前检查 bucket 是否存在；存在但无权访问或位于其他区域时返回错误
+#[command]
+pub async fn check_s3_bucket_exists(config: HashMap<String, String>) -> ApiResponse<bool> {
+    let client = match bucket_client(&config).await {
+        Ok(client) => client,
+        Err(e) => return ApiResponse::from_error("创建 S3 客户端失败", &e),
+    };
+    let bucket = client.bucket().to_string();
+
+    match client.head_bucket(&bucket).await {
+        Ok(BucketHead {
+            status: BucketStatus::Accessible,
+            ..
+        }) => ApiResponse::success(true),
+        Ok(BucketHead {
+            status: BucketStatus::NotFound,
+            ..
+        }) => ApiResponse::success(false),
+        Ok(BucketHead {
+            status: BucketStatus::Forbidden,
+            ..
+        }) => ApiResponse::from_error(
+            "检查 bucket 失败",
+            &Error::new(
+                ErrorKind::PermissionDenied,
+                "bucket 已存在但当前凭证无权访问",
+            ),
+        )
+        .with_param("bucket", &bucket),
+        Ok(BucketHead {
+            status: BucketStatus::WrongRegion,
+            region,
+        }) => {
+            let region = region.unwrap_or_default();
+            ApiResponse::from_error(
+                "检查 bucket 失败",
+                &Error::new_config(&format!("bucket 位于区域 {}，请修改区域配置", region)),
+            )
+            .with_param("bucket", &bucket)
+            .with_param("region", region)
+        }
+        Err(e) => ApiResponse::from_error("检查 bucket 失败", &e).with_param("bucket", &bucket),
+    }
+}
+
+/// 在配置的区域创建连接配置中的 bucket
+#[command]
+pub async fn create_s3_bucket(config: HashMap<String, String>) -> ApiResponse<bool> {
+    let client = match bucket_client(&config).await {
+        Ok(client) => client,
+        Err(e) => return ApiResponse::from_error("创建 S3 客户端失败", &e),
+    };
+    let bucket = client.bucket().to_string();
+
+    match client.create_bucket(&bucket).await {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => ApiResponse::from_error("创建 bucket 失败", &e)
+            .with_param("bucket", &bucket)
+            .with_param("region", client.region()),
+    }
+}
+
+/// 列出凭证可见的所有 bucket，`config` 中不需要 bucket
+#[command]
+pub async fn list_s3_buckets(config: HashMap<String, String>) -> ApiResponse<Vec<BucketInfo>> {
+    let client = match create_service_client(&config).await {
+        Ok(client) => client,
+        Err(e) => return ApiResponse::from_error("创建 S3 客户端失败", &e),
+    };
+    match client.list_buckets().await {
+        Ok(buckets) => ApiResponse::success(buckets),
+        Err(e) => ApiResponse::from_error("列出 bucket 失败", &e),
+    }
+}
+
+/// 列出 bucket 并补全所在区域，用于添加连接时选择 bucket
+#[command]
+pub async fn discover_s3_buckets(config: HashMap<String, String>) -> ApiResponse<Vec<BucketInfo>> {
+    let client = match create_service_client(&config).await {
+        Ok(client) => client,
+        Err(e) => return ApiResponse::from_error("创建 S3 客户端失败", &e),
+    };
+    match client.discover_buckets().await {
+        Ok(buckets) => ApiResponse::success(buckets),
+        Err(e) => ApiResponse::from_error("发现 bucket 失败", &e),
+    }
+}
+
+#[command]
+pub async fn head_s3_bucket(
+    config: HashMap<String, String>,
+    bucket: String,
+) -> ApiResponse<BucketHead> {
+    let client = match create_service_client(&config).await {
+        Ok(client) => client,
+        Err(e) => return ApiResponse::from_error("创建 S3 客户端失败", &e),
+    };
+    match client.head_bucket(&bucket).await {
+        Ok(head) => ApiResponse::success(head),
+        Err(e) => ApiResponse::from_error("查询 bucket 状态失败", &e).with_param("bucket", &bucket),
+    }
+}
+
+/// 删除空 bucket
+#[command]
+pub async fn delete_s3_bucket(
+    config: HashMap<String, String>,
+    bucket: String,
+) -> ApiResponse<bool> {
+    let client = match create_service_client(&config).await {
+        Ok(client) => client,
+        Err(e) => return ApiResponse::from_error("创建 S3 客户端失败", &e),
+    };
+    match client.delete_bucket(&bucket).await {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => ApiResponse::from_error("删除 bucket 失败", &e).with_param("bucket", &bucket),
+    }
+}
@@ -1,366 +1,441 @@
-use crate::core::file::FileManager;
-use crate::protocols::create_protocol;
-use tauri::command;
+use std::sync::Arc;
 
-use super::types::{ApiResponse, FileInfo, PaginatedFileList};
-use super::utils::get_connection_manager;
+use crate::core::error::Error;
+use crate::core::file::{local_dir_size, FileManager};
+use crate::core::transfer::{
+    ProgressTracker, TransferDirection, TransferReport, DEFAULT_TRANSFER_CONCURRENCY,
+};
+use tauri::{command, AppHandle, Emitter, Manager};
+
+use super::state::AppState;
+use super::types::{ApiResponse, FileInfo, PaginatedFileList, TransferProgressEvent};
+
+/// 传输进度事件名称，前端通过 listen 订阅
+pub const TRANSFER_PROGRESS_EVENT: &str = "transfer-progress";
+
+/// 从托管状态中取出连接对应的（缓存的）文件管理器，失败时给出可直接返回的错误响应
+fn open_connection<T>(
+    app: &AppHandle,
+    connection_id: &str,
+) -> Result<Arc<FileManager>, ApiResponse<T>> {
+    app.state::<AppState>()
+        .file_manager(connection_id)
+        .map_err(|e| ApiResponse::connection_error(connection_id, &e))
+}
+
+/// 将操作错误转换为错误响应；凭证失效时同时丢弃连接缓存的操作符，下次使用时重新获取凭证
+fn operation_error<T>(
+    app: &AppHandle,
+    connection_id: &str,
+    message: &str,
+    error: &Error,
+) -> ApiResponse<T> {
+    app.state::<AppState>()
+        .invalidate_on_auth_error(connection_id, error);
+    ApiResponse::from_error(message, error)
+}
 
 #[command]
-pub async fn list_files(connection_id: String, path: String) -> ApiResponse<Vec<FileInfo>> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager.list(&path).await {
-                            Ok(entries) => {
-                                let files: Vec<FileInfo> =
-                                    entries.into_iter().map(|entry| entry.into()).collect();
-                                ApiResponse::success(files)
-                            }
-                            Err(e) => ApiResponse::error(format!("列出文件失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
-            },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+pub async fn list_files(
+    app: AppHandle,
+    connection_id: String,
+    path: String,
+) -> ApiResponse<Vec<FileInfo>> {
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager.list(&path).await {
+        Ok(entries) => {
+            let files: Vec<FileInfo> = entries.into_iter().map(|entry| entry.into()).collect();
+            ApiResponse::success(files)
+        }
+        Err(e) => {
+            operation_error(&app, &connection_id, "列出文件失败", &e).with_param("path", &path)
+        }
     }
 }
 
 #[command]
 pub async fn list_files_paginated(
+    app: AppHandle,
     connection_id: String,
     path: String,
     page: usize,
     page_size: usize,
 ) -> ApiResponse<PaginatedFileList> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager.list_paginated(&path, page, page_size).await {
-                            Ok((entries, total)) => {
-                                let files: Vec<FileInfo> =
-                                    entries.into_iter().map(|entry| entry.into()).collect();
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager.list_paginated(&path, page, page_size).await {
+        Ok((entries, total)) => {
+            let files: Vec<FileInfo> = entries.into_iter().map(|entry| entry.into()).collect();
 
-                                let paginated_list = PaginatedFileList {
-                                    files,
-                                    total,
-                                    page,
-                                    page_size,
-                                    has_more: (page + 1) * page_size < total,
-                                };
+            let paginated_list = PaginatedFileList {
+                files,
+                total,
+                page,
+                page_size,
+                has_more: (page + 1) * page_size < total,
+            };
 
-                                ApiResponse::success(paginated_list)
-                            }
-                            Err(e) => ApiResponse::error(format!("分页列出文件失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
-            },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+            ApiResponse::success(paginated_list)
+        }
+        Err(e) => {
+            operation_error(&app, &connection_id, "分页列出文件失败", &e).with_param("path", &path)
+        }
     }
 }
 
 #[command]
 pub async fn upload_file(
+    app: AppHandle,
     connection_id: String,
     local_path: String,
     remote_path: String,
 ) -> ApiResponse<bool> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager
-                            .upload(std::path::Path::new(&local_path), &remote_path)
-                            .await
-                        {
-                            Ok(_) => ApiResponse::success(true),
-                            Err(e) => ApiResponse::error(format!("上传文件失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    let local = std::path::Path::new(&local_path);
+    if local.is_dir() {
+        // 目录上传按已完成文件的字节数汇总进度，与单文件上传使用同一个事件
+        let mut tracker = ProgressTracker::new(
+            TransferDirection::Upload,
+            &remote_path,
+            local_dir_size(local).unwrap_or(0),
+        );
+        return match file_manager
+            .upload_dir(local, &remote_path, DEFAULT_TRANSFER_CONCURRENCY, |batch| {
+                let _ = app.emit(
+                    TRANSFER_PROGRESS_EVENT,
+                    TransferProgressEvent::new(&connection_id, &tracker.advance(batch.result.size)),
+                );
+            })
+            .await
+        {
+            Ok(report) => match report.failed().next() {
+                None => ApiResponse::success(true),
+                Some(first) => ApiResponse::from_error(
+                    "上传目录失败",
+                    &Error::new_other(&format!(
+                        "{} 个文件失败，如 {}: {}",
+                        report.failed().count(),
+                        first.source,
+                        first.error.as_deref().unwrap_or_default()
+                    )),
+                )
+                .with_param("path", &remote_path)
+                .with_param("failed", report.failed().count()),
             },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+            Err(e) => operation_error(&app, &connection_id, "上传目录失败", &e)
+                .with_param("path", &remote_path),
+        };
+    }
+
+    match file_manager
+        .upload_with_progress(local, &remote_path, |progress| {
+            let _ = app.emit(
+                TRANSFER_PROGRESS_EVENT,
+                TransferProgressEvent::new(&connection_id, progress),
+            );
+        })
+        .await
+    {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => operation_error(&app, &connection_id, "上传文件失败", &e)
+            .with_param("path", &remote_path),
     }
 }
 
 #[command]
 pub async fn download_file(
+    app: AppHandle,
     connection_id: String,
     remote_path: String,
     local_path: String,
 ) -> ApiResponse<bool> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager
-                            .download(&remote_path, std::path::Path::new(&local_path))
-                            .await
-                        {
-                            Ok(_) => ApiResponse::success(true),
-                            Err(e) => ApiResponse::error(format!("下载文件失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager
+        .download_with_progress(
+            &remote_path,
+            std::path::Path::new(&local_path),
+            |progress| {
+                let _ = app.emit(
+                    TRANSFER_PROGRESS_EVENT,
+                    TransferProgressEvent::new(&connection_id, progress),
+                );
             },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+        )
+        .await
+    {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => operation_error(&app, &connection_id, "下载文件失败", &e)
+            .with_param("path", &remote_path),
     }
 }
 
 #[command]
-pub async fn delete_file(connection_id: String, path: String) -> ApiResponse<bool> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager.delete(&path).await {
-                            Ok(_) => ApiResponse::success(true),
-                            Err(e) => ApiResponse::error(format!("删除文件失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
-            },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+pub async fn delete_file(app: AppHandle, connection_id: String, path: String) -> ApiResponse<bool> {
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    // 目录路径以 '/' 结尾，需要递归删除其中的内容
+    let result = if path.ends_with('/') {
+        file_manager.delete_recursive(&path).await
+    } else {
+        file_manager.delete(&path).await
+    };
+    match result {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => {
+            operation_error(&app, &connection_id, "删除文件失败", &e).with_param("path", &path)
+        }
     }
 }
 
 #[command]
-pub async fn create_directory(connection_id: String, path: String) -> ApiResponse<bool> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        let dir_path = if path.ends_with('/') {
-                            path
-                        } else {
-                            format!("{}/", path)
-                        };
-                        match file_manager.create_dir(&dir_path).await {
-                            Ok(_) => ApiResponse::success(true),
-                            Err(e) => ApiResponse::error(format!("创建目录失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
-            },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+pub async fn copy_file(
+    app: AppHandle,
+    connection_id: String,
+    source_path: String,
+    target_path: String,
+) -> ApiResponse<bool> {
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager.copy(&source_path, &target_path).await {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => operation_error(&app, &connection_id, "复制文件失败", &e)
+            .with_param("path", &source_path),
     }
 }
 
 #[command]
-pub async fn get_directory_count(connection_id: String, path: String) -> ApiResponse<usize> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager.list(&path).await {
-                            Ok(entries) => ApiResponse::success(entries.len()),
-                            Err(e) => ApiResponse::error(format!("获取目录文件数失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
-            },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+pub async fn rename_file(
+    app: AppHandle,
+    connection_id: String,
+    source_path: String,
+    target_path: String,
+) -> ApiResponse<bool> {
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager.rename(&source_path, &target_path).await {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => operation_error(&app, &connection_id, "移动文件失败", &e)
+            .with_param("path", &source_path),
+    }
+}
+
+/// 在两个连接之间直接传输文件或目录，进度事件以目标连接 ID 发送
+#[command]
+pub async fn transfer_between_connections(
+    app: AppHandle,
+    source_connection_id: String,
+    source_path: String,
+    target_connection_id: String,
+    target_path: String,
+) -> ApiResponse<TransferReport> {
+    let source = match open_connection(&app, &source_connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    let target = match open_connection(&app, &target_connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+
+    match source
+        .transfer_to(&source_path, &target, &target_path, |progress| {
+            let _ = app.emit(
+                TRANSFER_PROGRESS_EVENT,
+                TransferProgressEvent::new(&target_connection_id, progress),
+            );
+        })
+        .await
+    {
+        Ok(report) => ApiResponse::success(report),
+        Err(e) => {
+            let state = app.state::<AppState>();
+            state.invalidate_on_auth_error(&source_connection_id, &e);
+            state.invalidate_on_auth_error(&target_connection_id, &e);
+            ApiResponse::from_error("传输文件失败", &e).with_param("path", &source_path)
+        }
+    }
+}
+
+#[command]
+pub async fn create_directory(
+    app: AppHandle,
+    connection_id: String,
+    path: String,
+) -> ApiResponse<bool> {
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    let dir_path = if path.ends_with('/') {
+        path
+    } else {
+        format!("{}/", path)
+    };
+    match file_manager.create_dir(&dir_path).await {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => {
+            operation_error(&app, &connection_id, "创建目录失败", &e).with_param("path", &dir_path)
+        }
+    }
+}
+
+#[command]
+pub async fn get_directory_count(
+    app: AppHandle,
+    connection_id: String,
+    path: String,
+) -> ApiResponse<usize> {
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager.list(&path).await {
+        Ok(entries) => ApiResponse::success(entries.len()),
+        Err(e) => operation_error(&app, &connection_id, "获取目录文件数失败", &e)
+            .with_param("path", &path),
     }
 }
 
 #[command]
 pub async fn search_files(
+    app: AppHandle,
     connection_id: String,
     path: String,
     query: String,
     page: usize,
     page_size: usize,
 ) -> ApiResponse<PaginatedFileList> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager
-                            .search_paginated(&path, &query, page, page_size)
-                            .await
-                        {
-                            Ok((entries, total)) => {
-                                let files: Vec<FileInfo> =
-                                    entries.into_iter().map(|entry| entry.into()).collect();
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager
+        .search_paginated(&path, &query, page, page_size)
+        .await
+    {
+        Ok((entries, total)) => {
+            let files: Vec<FileInfo> = entries.into_iter().map(|entry| entry.into()).collect();
 
-                                let paginated_list = PaginatedFileList {
-                                    files,
-                                    total,
-                                    page,
-                                    page_size,
-                                    has_more: (page + 1) * page_size < total,
-                                };
+            let paginated_list = PaginatedFileList {
+                files,
+                total,
+                page,
+                page_size,
+                has_more: (page + 1) * page_size < total,
+            };
 
-                                ApiResponse::success(paginated_list)
-                            }
-                            Err(e) => ApiResponse::error(format!("搜索文件失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
-            },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+            ApiResponse::success(paginated_list)
+        }
+        Err(e) => {
+            operation_error(&app, &connection_id, "搜索文件失败", &e).with_param("path", &path)
+        }
     }
 }
 
 #[command]
 pub async fn get_file_content(
+    app: AppHandle,
     connection_id: String,
     path: String,
     r#type: String, // 使用 r#type 因为 type 是 Rust 关键字
 ) -> ApiResponse<serde_json::Value> {
-    match get_connection_manager() {
-        Ok(manager) => {
-            match manager.get_connection(&connection_id) {
-                Some(config) => {
-                    match create_protocol(&config.protocol_type, &config.config) {
-                        Ok(protocol) => {
-                            match protocol.create_operator() {
-                                Ok(operator) => {
-                                    let file_manager = FileManager::new(operator);
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
 
-                                    // 检查文件大小限制（5MB）
-                                    match file_manager.get_file_info(&path).await {
-                                        Ok(Some(info)) => {
-                                            if let Some(size) = info.size {
-                                                if size > 5 * 1024 * 1024 {
-                                                    return ApiResponse::error(
-                                                        "文件太大，无法预览（限制5MB）".to_string(),
-                                                    );
-                                                }
-                                            }
-                                        }
-                                        Ok(None) => {
-                                            return ApiResponse::error("文件不存在".to_string());
-                                        }
-                                        Err(e) => {
-                                            return ApiResponse::error(format!(
-                                                "获取文件信息失败: {}",
-                                                e
-                                            ));
-                                        }
-                                    }
+    // 检查文件大小限制（5MB）
+    match file_manager.get_file_info(&path).await {
+        Ok(Some(info)) => {
+            if let Some(size) = info.size {
+                if size > 5 * 1024 * 1024 {
+                    return ApiResponse::from_error(
+                        "预览文件失败",
+                        &Error::new_not_supported("文件太大，无法预览（限制5MB）"),
+                    )
+                    .with_param("path", &path)
+                    .with_param("limit", 5 * 1024 * 1024);
+                }
+            }
+        }
+        Ok(None) => {
+            return ApiResponse::from_error("预览文件失败", &Error::new_not_found("文件不存在"))
+                .with_param("path", &path);
+        }
+        Err(e) => {
+            return operation_error(&app, &connection_id, "获取文件信息失败", &e)
+                .with_param("path", &path);
+        }
+    }
 
-                                    match file_manager.read_file(&path).await {
-                                        Ok(content) => {
-                                            let bytes = content.to_bytes().to_vec();
+    match file_manager.read_file(&path).await {
+        Ok(content) => {
+            let bytes = content.to_bytes().to_vec();
 
-                                            if r#type == "binary" {
-                                                // 对于二进制文件，返回字节数组
-                                                ApiResponse::success(serde_json::Value::Array(
-                                                    bytes
-                                                        .into_iter()
-                                                        .map(|b| {
-                                                            serde_json::Value::Number(b.into())
-                                                        })
-                                                        .collect(),
-                                                ))
-                                            } else {
-                                                // 对于文本文件，尝试转换为 UTF-8 字符串
-                                                match String::from_utf8(bytes) {
-                                                    Ok(text) => ApiResponse::success(
-                                                        serde_json::Value::String(text),
-                                                    ),
-                                                    Err(_) => {
-                                                        // 如果不是有效的UTF-8，尝试其他编码或返回错误
-                                                        ApiResponse::error("文件不是有效的UTF-8格式，请尝试二进制预览".to_string())
-                                                    }
-                                                }
-                                            }
-                                        }
-                                        Err(e) => {
-                                            ApiResponse::error(format!("读取文件失败: {}", e))
-                                        }
-                                    }
-                                }
-                                Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                            }
-                        }
-                        Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
+            if r#type == "binary" {
+                // 对于二进制文件，返回字节数组
+                ApiResponse::success(serde_json::Value::Array(
+                    bytes
+                        .into_iter()
+                        .map(|b| serde_json::Value::Number(b.into()))
+                        .collect(),
+                ))
+            } else {
+                // 对于文本文件，尝试转换为 UTF-8 字符串
+                match String::from_utf8(bytes) {
+                    Ok(text) => ApiResponse::success(serde_json::Value::String(text)),
+                    Err(_) => {
+                        // 如果不是有效的UTF-8，尝试其他编码或返回错误
+                        ApiResponse::from_error(
+                            "预览文件失败",
+                            &Error::new_not_supported("文件不是有效的UTF-8格式，请尝试二进制预览"),
+                        )
+                        .with_param("path", &path)
                     }
                 }
-                None => ApiResponse::error("Connection not found".to_string()),
             }
         }
-        Err(e) => ApiResponse::error(e.to_string()),
+        Err(e) => {
+            operation_error(&app, &connection_id, "读取文件失败", &e).with_param("path", &path)
+        }
     }
 }
 
 #[command]
 pub async fn batch_download_files(
+    app: AppHandle,
     connection_id: String,
     file_paths: Vec<String>,
     save_path: String,
 ) -> ApiResponse<bool> {
-    match get_connection_manager() {
-        Ok(manager) => match manager.get_connection(&connection_id) {
-            Some(config) => match create_protocol(&config.protocol_type, &config.config) {
-                Ok(protocol) => match protocol.create_operator() {
-                    Ok(operator) => {
-                        let file_manager = FileManager::new(operator);
-                        match file_manager
-                            .batch_download_as_zip(&file_paths, &save_path)
-                            .await
-                        {
-                            Ok(_) => ApiResponse::success(true),
-                            Err(e) => ApiResponse::error(format!("批量下载失败: {}", e)),
-                        }
-                    }
-                    Err(e) => ApiResponse::error(format!("创建操作符失败: {}", e)),
-                },
-                Err(e) => ApiResponse::error(format!("创建协议失败: {}", e)),
-            },
-            None => ApiResponse::error("Connection not found".to_string()),
-        },
-        Err(e) => ApiResponse::error(e.to_string()),
+    let file_manager = match open_connection(&app, &connection_id) {
+        Ok(file_manager) => file_manager,
+        Err(response) => return response,
+    };
+    match file_manager
+        .batch_download_as_zip(&file_paths, &save_path)
+        .await
+    {
+        Ok(_) => ApiResponse::success(true),
+        Err(e) => {
+            operation_error(&app, &connection_id, "批量下载失败", &e).with_param("path", &save_path)
+        }
     }
 }
@@ -1,5 +1,7 @@
+pub mod bucket;
 pub mod config;
 pub mod connection;
 pub mod file;
+pub mod state;
 pub mod types;
 pub mod utils;
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use log::{debug, error, warn};
+
+use crate::core::config::ConnectionManager;
+use crate::core::error::{Error, ErrorKind, Result};
+use crate::core::file::FileManager;
+use crate::core::operator;
+use crate::protocols::s3::credentials::S3Auth;
+
+use super::utils::connection_config_path;
+
+/// Tauri 托管的应用状态：连接配置与已建立的操作符缓存
+///
+/// 操作符以文件管理器的形式缓存（连同协议能力与连接限速器），
+/// 同一连接的多次命令复用底层连接池，连接配置变化时需调用 [`AppState::invalidate`]。
+pub struct AppState {
+    connections: Mutex<ConnectionManager>,
+    cache: Mutex<OperatorCache>,
+}
+
+/// 缓存的文件管理器，以及每个连接被丢弃缓存的次数
+///
+/// 文件管理器在缓存锁之外创建，插入前比较代数，
+/// 避免创建期间连接配置被更新后又把按旧配置创建的操作符放回缓存。
+#[derive(Default)]
+struct OperatorCache {
+    file_managers: HashMap<String, Arc<FileManager>>,
+    generations: HashMap<String, u64>,
+}
+
+impl OperatorCache {
+    fn generation(&self, connection_id: &str) -> u64 {
+        self.generations.get(connection_id).copied().unwrap_or(0)
+    }
+}
+
+impl AppState {
+    pub fn new(connections: ConnectionManager) -> Self {
+        Self {
+            connections: Mutex::new(connections),
+            cache: Mutex::new(OperatorCache::default()),
+        }
+    }
+
+    /// 从默认配置文件加载连接，失败时记录错误并以空连接列表启动
+    pub fn load() -> Self {
+        match connection_config_path() {
+            Ok(config_path) => Self::load_from(config_path),
+            Err(e) => {
+                error!("无法确定连接配置文件位置: {}, 将以空连接列表启动", e);
+                let fallback = std::env::temp_dir().join("mpfm").join("connections.json");
+                Self::new(ConnectionManager::empty(fallback))
+            }
+        }
+    }
+
+    /// 从指定配置文件加载连接
+    ///
+    /// 配置文件无法读取时先备份为 `.bak`，避免之后保存连接时覆盖用户原有的配置。
+    pub fn load_from(config_path: PathBuf) -> Self {
+        match ConnectionManager::new(config_path.clone()) {
+            Ok(connections) => Self::new(connections),
+            Err(e) => {
+                error!(
+                    "加载连接配置失败: {:?}: {}, 将以空连接列表启动",
+                    config_path, e
+                );
+                if config_path.is_file() {
+                    let backup = config_path.with_extension("json.bak");
+                    match std::fs::copy(&config_path, &backup) {
+                        Ok(_) => warn!("已将无法读取的连接配置备份到 {:?}", backup),
+                        Err(e) => warn!("备份连接配置失败: {}", e),
+                    }
+                }
+                Self::new(ConnectionManager::empty(config_path))
+            }
+        }
+    }
+
+    /// 获取连接管理器；持有期间不要跨越 await
+    pub fn connections(&self) -> MutexGuard<'_, ConnectionManager> {
+        self.connections.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 获取连接对应的文件管理器，首次使用时创建并缓存
+    pub fn file_manager(&self, connection_id: &str) -> Result<Arc<FileManager>> {
+        let generation = {
+            let cache = self.cache();
+            if let Some(file_manager) = cache.file_managers.get(connection_id) {
+                return Ok(file_manager.clone());
+            }
+            cache.generation(connection_id)
+        };
+
+        // 创建期间不持有缓存锁，避免与连接管理器的锁交叉
+        let protocol = {
+            let connections = self.connections();
+            let config = connections.get_connection(connection_id).ok_or_else(|| {
+                Error::new_not_found(&format!("连接 ID 不存在: {}", connection_id))
+            })?;
+            crate::protocols::create_protocol(&config.protocol_type, &config.config)?
+        };
+
+        debug!("创建并缓存操作符: {}", connection_id);
+        let file_manager = Arc::new(operator::create_file_manager(protocol.as_ref())?);
+        Ok(self.insert(connection_id, generation, file_manager))
+    }
+
+    /// 缓存代数未变时插入文件管理器；期间连接被丢弃过缓存则只用于本次调用
+    fn insert(
+        &self,
+        connection_id: &str,
+        generation: u64,
+        file_manager: Arc<FileManager>,
+    ) -> Arc<FileManager> {
+        let mut cache = self.cache();
+        if cache.generation(connection_id) != generation {
+            debug!(
+                "连接 {} 的配置在创建操作符期间已变化，不缓存",
+                connection_id
+            );
+            return file_manager;
+        }
+        cache
+            .file_managers
+            .entry(connection_id.to_string())
+            .or_insert(file_manager)
+            .clone()
+    }
+
+    /// 连接配置更新或删除后丢弃缓存的操作符，下次使用时按新配置重建
+    pub fn invalidate(&self, connection_id: &str) {
+        let mut cache = self.cache();
+        *cache
+            .generations
+            .entry(connection_id.to_string())
+            .or_default() += 1;
+        if cache.file_managers.remove(connection_id).is_some() {
+            debug!("已丢弃缓存的操作符: {}", connection_id);
+        }
+    }
+
+    /// 操作因凭证失效失败时丢弃缓存的操作符
+    ///
+    /// 只处理凭证会变化的 S3 连接（环境变量、profile、扮演角色）：操作符创建时已固定了凭证，
+    /// 重建后才能读取到新的凭证。静态密钥重建也无济于事，保留缓存。
+    pub fn invalidate_on_auth_error(&self, connection_id: &str, err: &Error) {
+        if err.kind() != ErrorKind::PermissionDenied {
+            return;
+        }
+        let refreshable = self
+            .connections()
+            .get_connection(connection_id)
+            .filter(|config| config.protocol_type == "s3")
+            .and_then(|config| S3Auth::from_config(&config.config).ok())
+            .is_some_and(|auth| auth.is_refreshable());
+        if refreshable {
+            warn!(
+                "连接 {} 凭证失效: {}, 下次使用时重新获取凭证",
+                connection_id, err
+            );
+            self.invalidate(connection_id);
+        }
+    }
+
+    fn cache(&self) -> MutexGuard<'_, OperatorCache> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ConnectionConfig;
+    use tempfile::TempDir;
+
+    fn create_state() -> (AppState, String, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = temp_dir.path().join("data");
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let mut manager = ConnectionManager::new(temp_dir.path().join("connections.json")).unwrap();
+        let config = ConnectionConfig::new(
+            "local".to_string(),
+            "fs".to_string(),
+            HashMap::from([(
+                "root_dir".to_string(),
+                data_dir.to_string_lossy().to_string(),
+            )]),
+        );
+        let id = config.id.clone();
+        manager.add_connection(config).unwrap();
+
+        (AppState::new(manager), id, temp_dir)
+    }
+
+    #[test]
+    fn test_file_manager_is_cached_until_invalidated() {
+        let (state, id, _temp_dir) = create_state();
+
+        let first = state.file_manager(&id).unwrap();
+        let second = state.file_manager(&id).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        state.invalidate(&id);
+        let third = state.file_manager(&id).unwrap();
+        assert!(!Arc::ptr_eq(&first, &third));
+    }
+
+    #[test]
+    fn test_invalidate_during_creation_is_not_cached() {
+        let (state, id, _temp_dir) = create_state();
+        let stale = state.file_manager(&id).unwrap();
+        state.invalidate(&id);
+
+        // 模拟创建期间配置被更新：按旧代数插入的操作符不进入缓存
+        let generation = state.cache().generation(&id);
+        state.invalidate(&id);
+        let returned = state.insert(&id, generation, stale.clone());
+        assert!(Arc::ptr_eq(&returned, &stale));
+        assert!(!state.cache().file_managers.contains_key(&id));
+
+        let fresh = state.file_manager(&id).unwrap();
+        assert!(!Arc::ptr_eq(&fresh, &stale));
+        assert!(Arc::ptr_eq(&fresh, &state.file_manager(&id).unwrap()));
+    }
+
+    #[test]
+    fn test_load_from_unreadable_config_starts_empty() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("connections.json");
+        // 非 UTF-8 内容无法读取为字符串
+        std::fs::write(&config_path, [0xff, 0xfe, 0x00]).unwrap();
+
+        let state = AppState::load_from(config_path.clone());
+        assert!(state.connections().get_connections().is_empty());
+        assert_eq!(
+            std::fs::read(temp_dir.path().join("connections.json.bak")).unwrap(),
+            vec![0xff, 0xfe, 0x00]
+        );
+    }
+
+    #[test]
+    fn test_invalidate_on_auth_error_only_for_refreshable_s3() {
+        let (state, fs_id, _temp_dir) = create_state();
+        let add_s3 = |source: &str| {
+            let config = ConnectionConfig::new(
+                format!("s3-{}", source),
+                "s3".to_string(),
+                HashMap::from([
+                    ("bucket".to_string(), "data".to_string()),
+                    ("region".to_string(), "us-east-1".to_string()),
+                    ("credential_source".to_string(), source.to_string()),
+                    ("access_key".to_string(), "ak".to_string()),
+                    ("secret_key".to_string(), "sk".to_string()),
+                ]),
+            );
+            let id = config.id.clone();
+            state.connections().add_connection(config).unwrap();
+            id
+        };
+        let env_id = add_s3("env");
+        let static_id = add_s3("static");
+
+        // 用本地连接的文件管理器占位，只关心缓存是否被丢弃
+        let file_manager = state.file_manager(&fs_id).unwrap();
+        for id in [&env_id, &static_id] {
+            state
+                .cache()
+                .file_managers
+                .insert(id.clone(), file_manager.clone());
+        }
+
+        let denied = Error::new(ErrorKind::PermissionDenied, "AccessDenied");
+        state.invalidate_on_auth_error(&env_id, &Error::new_not_found("missing"));
+        assert!(state.cache().file_managers.contains_key(&env_id));
+
+        state.invalidate_on_auth_error(&env_id, &denied);
+        state.invalidate_on_auth_error(&static_id, &denied);
+        state.invalidate_on_auth_error(&fs_id, &denied);
+        assert!(!state.cache().file_managers.contains_key(&env_id));
+        assert!(state.cache().file_managers.contains_key(&static_id));
+        assert!(state.cache().file_managers.contains_key(&fs_id));
+    }
+
+    #[test]
+    fn test_file_manager_unknown_connection() {
+        let (state, _id, _temp_dir) = create_state();
+        let err = state.file_manager("missing").err().unwrap();
+        assert_eq!(err.kind(), crate::core::error::ErrorKind::NotFound);
+    }
+}
@@ -1,4 +1,6 @@
 use crate::core::config::ConnectionConfig;
+use crate::core::error::{Error, ErrorKind};
+use crate::core::transfer::TransferProgress;
 use opendal::Entry;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -48,11 +50,17 @@ impl From<Entry> for FileInfo {
     }
 }
 
+/// 命令返回值
+///
+/// 失败时 `code` 为机器可读的错误码（见 `ErrorKind::code`），`params` 携带错误相关参数
+/// （如 `detail`、`connection_id`、`path`），前端可据此本地化错误信息。
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    pub code: Option<String>,
+    pub params: Option<HashMap<String, String>>,
 }
 
 impl<T> ApiResponse<T> {
@@ -61,6 +69,8 @@ impl<T> ApiResponse<T> {
             success: true,
             data: Some(data),
             error: None,
+            code: None,
+            params: None,
         }
     }
 
@@ -69,8 +79,44 @@ impl<T> ApiResponse<T> {
             success: false,
             data: None,
             error: Some(error),
+            code: Some(ErrorKind::Other.code().to_string()),
+            params: None,
         }
     }
+
+    /// 根据核心错误创建失败响应，错误码取自错误类型，原始错误信息放在 `detail` 参数中
+    pub fn from_error(context: &str, err: &Error) -> Self {
+        Self {
+            success: false,
+            data: None,
+            error: Some(format!("{}: {}", context, err)),
+            code: Some(err.kind().code().to_string()),
+            params: None,
+        }
+        .with_param("detail", err)
+    }
+
+    /// 连接不存在
+    pub fn connection_not_found(connection_id: &str) -> Self {
+        Self::from_error(
+            "连接不存在",
+            &Error::new_not_found(&format!("连接 ID 不存在: {}", connection_id)),
+        )
+        .with_param("connection_id", connection_id)
+    }
+
+    /// 无法打开连接（连接不存在、配置无效等）
+    pub fn connection_error(connection_id: &str, err: &Error) -> Self {
+        Self::from_error("打开连接失败", err).with_param("connection_id", connection_id)
+    }
+
+    /// 添加错误参数
+    pub fn with_param(mut self, key: &str, value: impl ToString) -> Self {
+        self.params
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -81,3 +127,46 @@ pub struct PaginatedFileList {
     pub page_size: usize,
     pub has_more: bool,
 }
+
+/// 推送给前端的传输进度事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProgressEvent {
+    pub connection_id: String,
+    #[serde(flatten)]
+    pub progress: TransferProgress,
+}
+
+impl TransferProgressEvent {
+    pub fn new(connection_id: &str, progress: &TransferProgress) -> Self {
+        Self {
+            connection_id: connection_id.to_string(),
+            progress: progress.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_error_sets_code_and_params() {
+        let response: ApiResponse<bool> =
+            ApiResponse::from_error("删除文件失败", &Error::new_not_found("missing"))
+                .with_param("path", "/a.txt");
+
+        assert!(!response.success);
+        assert_eq!(response.code.as_deref(), Some("not_found"));
+        let params = response.params.unwrap();
+        assert_eq!(params["detail"], "missing");
+        assert_eq!(params["path"], "/a.txt");
+    }
+
+    #[test]
+    fn test_connection_not_found() {
+        let response: ApiResponse<bool> = ApiResponse::connection_not_found("abc");
+
+        assert_eq!(response.code.as_deref(), Some("not_found"));
+        assert_eq!(response.params.unwrap()["connection_id"], "abc");
+    }
+}
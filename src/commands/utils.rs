@@ -1,11 +1,10 @@
-use crate::core::config::ConnectionManager;
+use std::path::PathBuf;
 
-/// 获取连接管理器实例
-pub fn get_connection_manager() -> Result<ConnectionManager, crate::core::error::Error> {
+/// 获取默认的连接配置文件路径
+pub fn connection_config_path() -> Result<PathBuf, crate::core::error::Error> {
     let config_dir = dirs::config_dir()
         .ok_or_else(|| crate::core::error::Error::new_other("无法获取配置目录"))?
         .join("mpfm");
 
-    let config_file = config_dir.join("connections.json");
-    ConnectionManager::new(config_file)
+    Ok(config_dir.join("connections.json"))
 }
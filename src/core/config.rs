@@ -76,6 +76,14 @@ impl ConnectionManager {
         })
     }
 
+    /// 创建不读取配置文件的空连接管理器，保存时写入指定路径
+    pub fn empty(config_path: PathBuf) -> Self {
+        Self {
+            config_path,
+            connections: HashMap::new(),
+        }
+    }
+
     /// 保存连接配置
     pub fn save_connections(&self) -> Result<()> {
         let configs: Vec<ConnectionConfig> = self.connections.values().cloned().collect();
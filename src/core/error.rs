@@ -1,59 +1,134 @@
 use std::fmt;
 
+use serde::{Deserialize, Serialize};
+
+/// 错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Io,
+    Config,
+    Protocol,
+    NotFound,
+    NotSupported,
+    PermissionDenied,
+    AlreadyExists,
+    RateLimited,
+    Network,
+    Other,
+}
+
+impl ErrorKind {
+    /// 机器可读的错误码，供前端区分错误类型
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::Io => "io",
+            ErrorKind::Config => "config",
+            ErrorKind::Protocol => "protocol",
+            ErrorKind::NotFound => "not_found",
+            ErrorKind::NotSupported => "not_supported",
+            ErrorKind::PermissionDenied => "permission_denied",
+            ErrorKind::AlreadyExists => "already_exists",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::Network => "network",
+            ErrorKind::Other => "other",
+        }
+    }
+
+    /// 命令行退出码
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ErrorKind::Other => 1,
+            ErrorKind::Config => 2,
+            ErrorKind::NotFound => 3,
+            ErrorKind::PermissionDenied => 4,
+            ErrorKind::AlreadyExists => 5,
+            ErrorKind::NotSupported => 6,
+            ErrorKind::Network => 7,
+            ErrorKind::RateLimited => 8,
+            ErrorKind::Protocol => 9,
+            ErrorKind::Io => 10,
+        }
+    }
+}
+
+impl From<opendal::ErrorKind> for ErrorKind {
+    fn from(kind: opendal::ErrorKind) -> Self {
+        match kind {
+            opendal::ErrorKind::NotFound => ErrorKind::NotFound,
+            opendal::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            opendal::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+            opendal::ErrorKind::Unsupported => ErrorKind::NotSupported,
+            opendal::ErrorKind::ConfigInvalid => ErrorKind::Config,
+            opendal::ErrorKind::RateLimited => ErrorKind::RateLimited,
+            opendal::ErrorKind::IsADirectory | opendal::ErrorKind::NotADirectory => ErrorKind::Io,
+            _ => ErrorKind::Protocol,
+        }
+    }
+}
+
+impl From<std::io::ErrorKind> for ErrorKind {
+    fn from(kind: std::io::ErrorKind) -> Self {
+        match kind {
+            std::io::ErrorKind::NotFound => ErrorKind::NotFound,
+            std::io::ErrorKind::PermissionDenied => ErrorKind::PermissionDenied,
+            std::io::ErrorKind::AlreadyExists => ErrorKind::AlreadyExists,
+            _ => ErrorKind::Io,
+        }
+    }
+}
+
 /// 错误结构体
 #[derive(Debug)]
 pub struct Error {
+    kind: ErrorKind,
     message: String,
     source: Option<Box<dyn std::error::Error + Send + Sync>>,
 }
 
 impl Error {
-    /// 创建新的 IO 错误
-    pub fn new_io(message: &str) -> Self {
+    /// 创建指定类型的错误
+    pub fn new(kind: ErrorKind, message: &str) -> Self {
         Self {
+            kind,
             message: message.to_string(),
             source: None,
         }
     }
 
+    /// 创建新的 IO 错误
+    pub fn new_io(message: &str) -> Self {
+        Self::new(ErrorKind::Io, message)
+    }
+
     /// 创建新的配置错误
     pub fn new_config(message: &str) -> Self {
-        Self {
-            message: message.to_string(),
-            source: None,
-        }
+        Self::new(ErrorKind::Config, message)
     }
 
     /// 创建新的协议错误
     pub fn new_protocol(message: &str) -> Self {
-        Self {
-            message: message.to_string(),
-            source: None,
-        }
+        Self::new(ErrorKind::Protocol, message)
     }
 
     /// 创建新的未找到错误
     pub fn new_not_found(message: &str) -> Self {
-        Self {
-            message: message.to_string(),
-            source: None,
-        }
+        Self::new(ErrorKind::NotFound, message)
     }
 
     /// 创建新的不支持错误
     pub fn new_not_supported(message: &str) -> Self {
-        Self {
-            message: message.to_string(),
-            source: None,
-        }
+        Self::new(ErrorKind::NotSupported, message)
     }
 
     /// 创建新的其他错误
     pub fn new_other(message: &str) -> Self {
-        Self {
-            message: message.to_string(),
-            source: None,
-        }
+        Self::new(ErrorKind::Other, message)
+    }
+
+    /// 错误类型
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
     }
 
     /// 添加源错误
@@ -81,13 +156,26 @@ impl std::error::Error for Error {
 // 转换自常见错误类型
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Self {
-        Self::new_io(&err.to_string()).with_source(err)
+        Self::new(err.kind().into(), &err.to_string()).with_source(err)
     }
 }
 
+/// S3 临时凭证过期的错误码，视为无权限
+pub(crate) const EXPIRED_TOKEN_CODES: [&str; 2] = ["ExpiredToken", "TokenRefreshRequired"];
+
 impl From<opendal::Error> for Error {
     fn from(err: opendal::Error) -> Self {
-        Self::new_protocol(&err.to_string()).with_source(err)
+        let message = err.to_string();
+        // OpenDAL 不识别凭证过期的错误码，只在错误信息中保留 S3 返回的 code 字段
+        let kind = if EXPIRED_TOKEN_CODES
+            .iter()
+            .any(|code| message.contains(&format!("code: \"{}\"", code)))
+        {
+            ErrorKind::PermissionDenied
+        } else {
+            err.kind().into()
+        };
+        Self::new(kind, &message).with_source(err)
     }
 }
 
@@ -97,6 +185,17 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        let kind = if err.is_connect() || err.is_timeout() {
+            ErrorKind::Network
+        } else {
+            ErrorKind::Protocol
+        };
+        Self::new(kind, &err.to_string()).with_source(err)
+    }
+}
+
 impl From<zip::result::ZipError> for Error {
     fn from(err: zip::result::ZipError) -> Self {
         Self::new_io(&err.to_string()).with_source(err)
@@ -105,3 +204,39 @@ impl From<zip::result::ZipError> for Error {
 
 // 定义结果类型别名
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constructors_set_kind() {
+        assert_eq!(Error::new_not_found("x").kind(), ErrorKind::NotFound);
+        assert_eq!(Error::new_config("x").kind(), ErrorKind::Config);
+        assert_eq!(Error::new_not_supported("x").kind().code(), "not_supported");
+    }
+
+    #[test]
+    fn test_from_opendal_error_maps_kind() {
+        let err: Error = opendal::Error::new(opendal::ErrorKind::NotFound, "missing").into();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let err: Error = opendal::Error::new(opendal::ErrorKind::Unexpected, "boom").into();
+        assert_eq!(err.kind(), ErrorKind::Protocol);
+
+        // S3 以 400 返回的凭证过期错误
+        let err: Error = opendal::Error::new(
+            opendal::ErrorKind::Unexpected,
+            r#"S3Error { code: "ExpiredToken", message: "The provided token has expired.", resource: "", request_id: "" }"#,
+        )
+        .into();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+    }
+
+    #[test]
+    fn test_from_io_error_maps_kind() {
+        let err: Error = std::io::Error::from(std::io::ErrorKind::PermissionDenied).into();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_ne!(err.kind().exit_code(), ErrorKind::NotFound.exit_code());
+    }
+}
@@ -1,13 +1,24 @@
 use std::fs::File;
-use std::io::{Read, Write};
-use std::path::Path;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
-use log::{debug, info};
+use futures::stream::BoxStream;
+use futures::{StreamExt, TryStreamExt};
+use log::{debug, info, warn};
 use opendal::{Entry, Metadata, Operator};
 use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use zip::{write::FileOptions, CompressionMethod, ZipWriter};
 
 use crate::core::error::{Error, Result};
+use crate::core::throttle::{RateLimiter, Throttle};
+use crate::core::transfer::{
+    BatchProgress, DownloadCheckpoint, ProgressTracker, TransferDirection, TransferProgress,
+    TransferReport, TransferResult,
+};
+use crate::core::walker::{self, WalkEntry, WalkEvent, WalkOptions};
+use crate::protocols::traits::Capabilities;
 
 /// 文件信息结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,15 +30,54 @@ pub struct FileInfo {
     pub modified: Option<String>,
 }
 
+/// 上传/下载时单个数据块的默认大小（8 MiB）
+pub const DEFAULT_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 /// 文件管理器结构体，提供高级文件操作接口
 pub struct FileManager {
     operator: Operator,
+    chunk_size: usize,
+    capabilities: Capabilities,
+    throttle: Throttle,
 }
 
 impl FileManager {
     /// 创建新的文件管理器
     pub fn new(operator: Operator) -> Self {
-        Self { operator }
+        Self {
+            operator,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            capabilities: Capabilities::default(),
+            throttle: Throttle::default(),
+        }
+    }
+
+    /// 设置底层协议的能力描述，用于选择批量删除等优化路径
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// 设置传输时使用的数据块大小
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// 添加带宽限制，可多次调用叠加全局限制与连接自身的限制
+    pub fn with_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.throttle = self.throttle.with_limiter(limiter);
+        self
+    }
+
+    /// 底层存储的协议
+    pub fn scheme(&self) -> opendal::Scheme {
+        self.operator.info().scheme()
+    }
+
+    /// 当前生效的带宽限制，供绕过文件管理器的传输路径（如 S3 分片上传）复用
+    pub fn throttle(&self) -> &Throttle {
+        &self.throttle
     }
 
     /// 列出给定路径下的文件和目录
@@ -88,8 +138,22 @@ impl FileManager {
         Ok((paginated_entries, total_count))
     }
 
-    /// 上传文件
+    /// 上传文件（分块流式写入，内存占用与文件大小无关）
     pub async fn upload(&self, local_path: &Path, remote_path: &str) -> Result<()> {
+        self.upload_with_progress(local_path, remote_path, |_| {})
+            .await
+    }
+
+    /// 上传文件，每写入一个数据块回调一次进度
+    pub async fn upload_with_progress<F>(
+        &self,
+        local_path: &Path,
+        remote_path: &str,
+        mut progress_callback: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&TransferProgress) + Send + Sync,
+    {
         debug!("上传文件: {} -> {}", local_path.display(), remote_path);
 
         if !local_path.exists() {
@@ -101,61 +165,304 @@ impl FileManager {
 
         let remote_path = normalize_path(remote_path);
 
-        let mut file = File::open(local_path)?;
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)?;
+        let mut file = tokio::fs::File::open(local_path).await?;
+        let total_size = file.metadata().await?.len();
+        let mut tracker = ProgressTracker::new(TransferDirection::Upload, &remote_path, total_size);
 
-        self.operator.write(&remote_path, buffer).await?;
+        let mut writer = self
+            .operator
+            .writer_with(&remote_path)
+            .chunk(self.chunk_size)
+            .await?;
+
+        // 限速时按较小的切片读取并申请额度，写入器仍按块大小上传
+        let mut buffer = vec![0u8; self.throttle.slice_size(self.chunk_size)];
+        loop {
+            let n = match file.read(&mut buffer).await {
+                Ok(n) => n,
+                Err(e) => {
+                    let _ = writer.abort().await;
+                    return Err(e.into());
+                }
+            };
+            if n == 0 {
+                break;
+            }
+
+            self.throttle.consume(n as u64).await;
+            if let Err(e) = writer.write(buffer[..n].to_vec()).await {
+                let _ = writer.abort().await;
+                return Err(e.into());
+            }
+            progress_callback(&tracker.advance(n as u64));
+        }
+
+        // 完成失败时同样中止，避免服务端留下未完成的分片上传
+        if let Err(e) = writer.close().await {
+            let _ = writer.abort().await;
+            return Err(e.into());
+        }
 
         info!("文件上传成功: {} -> {}", local_path.display(), remote_path);
         Ok(())
     }
 
-    /// 下载文件
-    pub async fn download(&self, remote_path: &str, local_path: &Path) -> Result<()> {
-        debug!("下载文件: {} -> {}", remote_path, local_path.display());
-
-        let remote_path = normalize_path(remote_path);
+    /// 递归上传本地目录：在远程重建目录结构，并发上传所有文件，返回每个文件的结果
+    pub async fn upload_dir<F>(
+        &self,
+        local_dir: &Path,
+        remote_dir: &str,
+        concurrency: usize,
+        mut progress_callback: F,
+    ) -> Result<TransferReport>
+    where
+        F: FnMut(&BatchProgress) + Send + Sync,
+    {
+        debug!("递归上传目录: {} -> {}", local_dir.display(), remote_dir);
 
-        // 检查远程文件是否存在
-        if !self.operator.exists(&remote_path).await? {
+        if !local_dir.is_dir() {
             return Err(Error::new_not_found(&format!(
-                "远程文件不存在: {}",
-                remote_path
+                "本地目录不存在: {}",
+                local_dir.display()
             )));
         }
 
-        // 创建本地目录（如果需要）
-        if let Some(parent) = local_path.parent() {
-            if !parent.exists() {
-                std::fs::create_dir_all(parent)?;
+        let LocalTree { dirs, files } = walk_local_dir(local_dir)?;
+        let remote_root = remote_dir.trim_end_matches('/');
+
+        // 先创建目录结构（父目录在前）
+        self.create_dir(&format!("{}/", remote_root)).await?;
+        for dir in &dirs {
+            self.create_dir(&format!("{}/{}/", remote_root, dir))
+                .await?;
+        }
+
+        let total = files.len();
+        info!(
+            "开始上传 {} 个文件到 {} (并发 {})",
+            total, remote_root, concurrency
+        );
+
+        let mut uploads = futures::stream::iter(files)
+            .map(|(local_path, relative)| async move {
+                let target = format!("{}/{}", remote_root, relative);
+                let size = std::fs::metadata(&local_path).map(|m| m.len()).unwrap_or(0);
+                let error = self
+                    .upload(&local_path, &target)
+                    .await
+                    .err()
+                    .map(|e| e.to_string());
+                TransferResult {
+                    source: local_path.display().to_string(),
+                    target,
+                    size,
+                    error,
+                }
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        let mut report = TransferReport::default();
+        while let Some(result) = uploads.next().await {
+            progress_callback(&BatchProgress {
+                completed: report.results.len() + 1,
+                total,
+                result: &result,
+            });
+            report.results.push(result);
+        }
+
+        info!(
+            "目录上传完成: 成功 {} 个，失败 {} 个",
+            report.succeeded().count(),
+            report.failed().count()
+        );
+        Ok(report)
+    }
+
+    /// 递归下载远程目录到本地：保留相对路径、空目录与修改时间，并发下载所有文件
+    ///
+    /// 包含 `..`、`.`、空段或绝对路径的远程键不会写入本地，记为失败项。
+    pub async fn download_dir<F>(
+        &self,
+        remote_dir: &str,
+        local_dir: &Path,
+        concurrency: usize,
+        mut progress_callback: F,
+    ) -> Result<TransferReport>
+    where
+        F: FnMut(&BatchProgress) + Send + Sync,
+    {
+        debug!("递归下载目录: {} -> {}", remote_dir, local_dir.display());
+
+        let entries: Vec<WalkEntry> = self
+            .walk(remote_dir, WalkOptions::new())
+            .try_filter_map(|event| async move { Ok(event.into_entry()?.map(|entry| *entry)) })
+            .try_collect()
+            .await?;
+        std::fs::create_dir_all(local_dir)?;
+
+        // 先创建目录（包括远程的空目录），无法安全映射到本地的条目直接记为失败
+        let mut rejected = Vec::new();
+        let mut files = Vec::new();
+        for entry in entries {
+            let local_path = match local_path_for(local_dir, &entry.relative) {
+                Ok(local_path) => local_path,
+                Err(e) => {
+                    warn!("跳过不安全的远程路径: {}: {}", entry.path, e);
+                    rejected.push(TransferResult {
+                        source: entry.path,
+                        target: local_dir.display().to_string(),
+                        size: 0,
+                        error: Some(e.to_string()),
+                    });
+                    continue;
+                }
+            };
+            if entry.is_dir() {
+                if let Err(e) = std::fs::create_dir_all(&local_path) {
+                    rejected.push(TransferResult {
+                        source: entry.path,
+                        target: local_path.display().to_string(),
+                        size: 0,
+                        error: Some(e.to_string()),
+                    });
+                }
+            } else {
+                files.push((entry.path, local_path));
             }
         }
 
-        let data = self.operator.read(&remote_path).await?;
-        let mut file = File::create(local_path)?;
-        file.write_all(&data.to_bytes())?;
+        let total = files.len() + rejected.len();
+        info!(
+            "开始下载 {} 个文件到 {} (并发 {})",
+            files.len(),
+            local_dir.display(),
+            concurrency
+        );
 
-        info!("文件下载成功: {} -> {}", remote_path, local_path.display());
-        Ok(())
+        let mut report = TransferReport::default();
+        for result in rejected {
+            progress_callback(&BatchProgress {
+                completed: report.results.len() + 1,
+                total,
+                result: &result,
+            });
+            report.results.push(result);
+        }
+
+        let mut downloads = futures::stream::iter(files)
+            .map(|(remote_path, local_path)| async move {
+                let (size, error) = match self
+                    .download_preserving_mtime(&remote_path, &local_path)
+                    .await
+                {
+                    Ok(size) => (size, None),
+                    Err(e) => (0, Some(e.to_string())),
+                };
+                TransferResult {
+                    source: remote_path,
+                    target: local_path.display().to_string(),
+                    size,
+                    error,
+                }
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        while let Some(result) = downloads.next().await {
+            progress_callback(&BatchProgress {
+                completed: report.results.len() + 1,
+                total,
+                result: &result,
+            });
+            report.results.push(result);
+        }
+
+        info!(
+            "目录下载完成: 成功 {} 个，失败 {} 个",
+            report.succeeded().count(),
+            report.failed().count()
+        );
+        Ok(report)
     }
 
-    /// 下载带进度的大文件
+    /// 下载单个文件并将本地修改时间设置为远程文件的修改时间，返回文件大小
+    async fn download_preserving_mtime(&self, remote_path: &str, local_path: &Path) -> Result<u64> {
+        let meta = self.stat(remote_path).await?;
+        // 复用已获取的元数据，避免下载时再次查询
+        self.download_inner(remote_path, local_path, false, Some(meta.clone()), |_| {})
+            .await?;
+
+        if let Some(modified) = meta.last_modified() {
+            let file = std::fs::File::options().write(true).open(local_path)?;
+            file.set_modified(std::time::SystemTime::from(modified))?;
+        }
+        Ok(meta.content_length())
+    }
+
+    /// 下载文件（分块流式读取，内存占用与文件大小无关）
+    pub async fn download(&self, remote_path: &str, local_path: &Path) -> Result<()> {
+        self.download_with_progress(remote_path, local_path, |_| {})
+            .await
+    }
+
+    /// 下载文件，每写入一个数据块回调一次进度
     pub async fn download_with_progress<F>(
         &self,
         remote_path: &str,
         local_path: &Path,
+        progress_callback: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&TransferProgress) + Send + Sync,
+    {
+        self.download_inner(remote_path, local_path, false, None, progress_callback)
+            .await
+    }
+
+    /// 断点续传下载：校验本地已有的部分文件后，从已下载的位置继续读取
+    pub async fn resume_download_with_progress<F>(
+        &self,
+        remote_path: &str,
+        local_path: &Path,
+        progress_callback: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&TransferProgress) + Send + Sync,
+    {
+        self.download_inner(remote_path, local_path, true, None, progress_callback)
+            .await
+    }
+
+    async fn download_inner<F>(
+        &self,
+        remote_path: &str,
+        local_path: &Path,
+        resume: bool,
+        metadata: Option<Metadata>,
         mut progress_callback: F,
     ) -> Result<()>
     where
-        F: FnMut(u64, u64) + Send + Sync,
+        F: FnMut(&TransferProgress) + Send + Sync,
     {
-        debug!("下载大文件: {} -> {}", remote_path, local_path.display());
+        debug!("下载文件: {} -> {}", remote_path, local_path.display());
 
         let remote_path = normalize_path(remote_path);
 
-        // 检查远程文件是否存在并获取文件大小
-        let metadata = self.operator.stat(&remote_path).await?;
+        // 获取远程文件大小，调用方已查询过元数据时直接复用
+        let metadata = match metadata {
+            Some(metadata) => metadata,
+            None => match self.operator.stat(&remote_path).await {
+                Ok(metadata) => metadata,
+                Err(e) if e.kind() == opendal::ErrorKind::NotFound => {
+                    return Err(Error::new_not_found(&format!(
+                        "远程文件不存在: {}",
+                        remote_path
+                    )));
+                }
+                Err(e) => return Err(e.into()),
+            },
+        };
+        let remote_state = DownloadCheckpoint::from_metadata(&metadata);
         let total_size = metadata.content_length();
 
         // 创建本地目录（如果需要）
@@ -165,18 +472,58 @@ impl FileManager {
             }
         }
 
-        // 直接读取全部数据
-        let data = self.operator.read(&remote_path).await?;
-        progress_callback(data.len() as u64, total_size);
+        let offset = if resume {
+            resume_offset(local_path, &remote_state)
+        } else {
+            0
+        };
+        let mut tracker =
+            ProgressTracker::new(TransferDirection::Download, &remote_path, total_size)
+                .resume_from(offset);
+
+        if offset > 0 && offset == total_size {
+            info!("本地文件已完整，无需续传: {}", local_path.display());
+            DownloadCheckpoint::remove(local_path)?;
+            progress_callback(&tracker.snapshot());
+            return Ok(());
+        }
+
+        // 记录远程文件标识，下载中断后可用于续传校验
+        remote_state.save(local_path)?;
 
-        let mut file = File::create(local_path)?;
-        file.write_all(&data.to_bytes())?;
+        let mut file = if offset > 0 {
+            info!("从 {} 字节处继续下载: {}", offset, remote_path);
+            tokio::fs::OpenOptions::new()
+                .append(true)
+                .open(local_path)
+                .await?
+        } else {
+            tokio::fs::File::create(local_path).await?
+        };
 
-        info!(
-            "大文件下载成功: {} -> {}",
-            remote_path,
-            local_path.display()
-        );
+        let mut stream = self
+            .operator
+            .reader_with(&remote_path)
+            .chunk(self.chunk_size)
+            .await?
+            .into_stream(offset..)
+            .await?;
+
+        let slice_size = self.throttle.slice_size(self.chunk_size);
+        while let Some(buffer) = stream.try_next().await? {
+            for bytes in buffer {
+                for slice in bytes.chunks(slice_size) {
+                    self.throttle.consume(slice.len() as u64).await;
+                    file.write_all(slice).await?;
+                    progress_callback(&tracker.advance(slice.len() as u64));
+                }
+            }
+        }
+
+        file.flush().await?;
+        DownloadCheckpoint::remove(local_path)?;
+
+        info!("文件下载成功: {} -> {}", remote_path, local_path.display());
         Ok(())
     }
 
@@ -191,6 +538,305 @@ impl FileManager {
         Ok(())
     }
 
+    /// 递归删除目录及其所有内容
+    ///
+    /// 协议支持批量删除时使用 `remove_all`，否则逐个删除，子项先于父目录删除。
+    pub async fn delete_recursive(&self, path: &str) -> Result<()> {
+        debug!("递归删除目录: {}", path);
+
+        let normalized = normalize_path(path).trim_end_matches('/').to_string();
+        if normalized.is_empty() {
+            return Err(Error::new_other("不允许删除根目录"));
+        }
+        let dir = format!("{}/", normalized);
+
+        if self.capabilities.can_batch_delete {
+            self.operator.remove_all(&dir).await?;
+        } else {
+            let mut paths: Vec<String> = self
+                .operator
+                .list_with(&dir)
+                .recursive(true)
+                .await?
+                .into_iter()
+                .map(|entry| entry.path().to_string())
+                .filter(|p| *p != dir)
+                .collect();
+            // 路径越深越先删除，保证目录删除时已为空
+            paths.sort_by_key(|p| std::cmp::Reverse(p.trim_end_matches('/').matches('/').count()));
+            paths.push(dir.clone());
+
+            for p in &paths {
+                debug!("删除: {}", p);
+                self.operator.delete(p).await?;
+            }
+        }
+
+        info!("目录删除成功: {}", dir);
+        Ok(())
+    }
+
+    /// 复制文件或目录（目录递归复制），协议支持时使用服务端复制
+    pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        debug!("复制: {} -> {}", from, to);
+        self.copy_or_move(from, to, false).await?;
+        info!("复制成功: {} -> {}", from, to);
+        Ok(())
+    }
+
+    /// 移动（重命名）文件或目录，协议支持时使用服务端重命名
+    pub async fn rename(&self, from: &str, to: &str) -> Result<()> {
+        debug!("移动: {} -> {}", from, to);
+        self.copy_or_move(from, to, true).await?;
+        info!("移动成功: {} -> {}", from, to);
+        Ok(())
+    }
+
+    async fn copy_or_move(&self, from: &str, to: &str, remove_source: bool) -> Result<()> {
+        let source = normalize_path(from).trim_end_matches('/').to_string();
+        let target = normalize_path(to);
+
+        if !self.is_dir_path(from).await? {
+            // 目标以 '/' 结尾或是已存在的目录时放到该目录下，保留原文件名
+            let file_name = source.rsplit('/').next().unwrap_or(&source);
+            let target = if target.ends_with('/') || target.is_empty() {
+                format!("{}{}", target, file_name)
+            } else if matches!(self.is_dir_path(&target).await, Ok(true)) {
+                format!("{}/{}", target, file_name)
+            } else {
+                target
+            };
+            // 不支持原生复制的后端会先截断目标再读取源文件，源与目标相同会丢失数据
+            if target == source {
+                return Err(Error::new_other(&format!(
+                    "不能将文件复制或移动到自身: {} -> {}",
+                    from, to
+                )));
+            }
+            return self
+                .copy_or_move_file(&source, &target, remove_source)
+                .await;
+        }
+
+        let target = target.trim_end_matches('/').to_string();
+        if source.is_empty() || target == source || target.starts_with(&format!("{}/", source)) {
+            return Err(Error::new_other(&format!(
+                "不能将目录复制或移动到自身: {} -> {}",
+                from, to
+            )));
+        }
+
+        let entries: Vec<Box<WalkEntry>> = self
+            .walk(from, WalkOptions::new())
+            .try_filter_map(|event| async move { event.into_entry() })
+            .try_collect()
+            .await?;
+
+        self.operator.create_dir(&format!("{}/", target)).await?;
+        for entry in entries {
+            let destination = format!("{}/{}", target, entry.relative);
+            if entry.is_dir() {
+                self.operator.create_dir(&destination).await?;
+            } else {
+                self.copy_or_move_file(&entry.path[1..], &destination, remove_source)
+                    .await?;
+            }
+        }
+
+        if remove_source {
+            self.delete_recursive(from).await?;
+        }
+        Ok(())
+    }
+
+    async fn copy_or_move_file(&self, from: &str, to: &str, remove_source: bool) -> Result<()> {
+        if remove_source && self.capabilities.can_rename {
+            self.operator.rename(from, to).await?;
+            return Ok(());
+        }
+
+        if self.capabilities.can_copy {
+            self.operator.copy(from, to).await?;
+        } else {
+            debug!("协议不支持服务端复制，使用读写方式: {} -> {}", from, to);
+            stream_copy(
+                &self.operator,
+                from,
+                &self.operator,
+                to,
+                self.chunk_size,
+                &self.throttle,
+                |_| {},
+            )
+            .await?;
+        }
+
+        if remove_source {
+            self.operator.delete(from).await?;
+        }
+        Ok(())
+    }
+
+    /// 将文件或目录直接流式传输到另一个连接（不经过本地临时文件），返回每个文件的结果
+    ///
+    /// 目标路径以 '/' 结尾且源为文件时，保留原文件名放入该目录。
+    pub async fn transfer_to<F>(
+        &self,
+        from: &str,
+        target: &FileManager,
+        to: &str,
+        mut progress_callback: F,
+    ) -> Result<TransferReport>
+    where
+        F: FnMut(&TransferProgress) + Send + Sync,
+    {
+        debug!("跨连接传输: {} -> {}", from, to);
+
+        let source = normalize_path(from).trim_end_matches('/').to_string();
+        let destination = normalize_path(to);
+        let mut report = TransferReport::default();
+
+        if !self.is_dir_path(from).await? {
+            let destination = if destination.ends_with('/') || destination.is_empty() {
+                format!(
+                    "{}{}",
+                    destination,
+                    source.rsplit('/').next().unwrap_or(&source)
+                )
+            } else {
+                destination
+            };
+            let result = self
+                .transfer_file_to(&source, target, &destination, &mut progress_callback)
+                .await;
+            report
+                .results
+                .push(transfer_result(&source, &destination, result));
+            return Ok(report);
+        }
+
+        let destination = destination.trim_end_matches('/');
+        let entries: Vec<Box<WalkEntry>> = self
+            .walk(from, WalkOptions::new())
+            .try_filter_map(|event| async move { event.into_entry() })
+            .try_collect()
+            .await?;
+
+        if !destination.is_empty() {
+            target
+                .operator
+                .create_dir(&format!("{}/", destination))
+                .await?;
+        }
+        for entry in entries {
+            let target_path = if destination.is_empty() {
+                entry.relative.clone()
+            } else {
+                format!("{}/{}", destination, entry.relative)
+            };
+            if entry.is_dir() {
+                target.operator.create_dir(&target_path).await?;
+                continue;
+            }
+
+            let result = self
+                .transfer_file_to(
+                    &entry.path[1..],
+                    target,
+                    &target_path,
+                    &mut progress_callback,
+                )
+                .await;
+            report
+                .results
+                .push(transfer_result(&entry.path, &target_path, result));
+        }
+
+        info!(
+            "跨连接传输完成: 成功 {} 个，失败 {} 个",
+            report.succeeded().count(),
+            report.failed().count()
+        );
+        Ok(report)
+    }
+
+    /// 将文件或目录移动到另一个连接：全部传输成功后才删除源，有失败项时保留源
+    pub async fn move_to<F>(
+        &self,
+        from: &str,
+        target: &FileManager,
+        to: &str,
+        progress_callback: F,
+    ) -> Result<TransferReport>
+    where
+        F: FnMut(&TransferProgress) + Send + Sync,
+    {
+        let is_dir = self.is_dir_path(from).await?;
+        let report = self
+            .transfer_to(from, target, to, progress_callback)
+            .await?;
+
+        if report.failed().count() > 0 {
+            warn!("部分文件传输失败，保留源: {}", from);
+        } else if is_dir {
+            self.delete_recursive(from).await?;
+        } else {
+            self.delete(from).await?;
+        }
+        Ok(report)
+    }
+
+    /// 将单个文件流式传输到另一个连接，返回传输的字节数
+    pub async fn transfer_file_to<F>(
+        &self,
+        from: &str,
+        target: &FileManager,
+        to: &str,
+        progress_callback: &mut F,
+    ) -> Result<u64>
+    where
+        F: FnMut(&TransferProgress) + Send + Sync,
+    {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        let total_size = self.operator.stat(&from).await?.content_length();
+        let mut tracker = ProgressTracker::new(TransferDirection::Copy, &to, total_size);
+
+        let copied = stream_copy(
+            &self.operator,
+            &from,
+            &target.operator,
+            &to,
+            self.chunk_size,
+            &self.throttle.merge(&target.throttle),
+            |len| progress_callback(&tracker.advance(len)),
+        )
+        .await?;
+
+        info!("文件传输成功: {} -> {}", from, to);
+        Ok(copied)
+    }
+
+    /// 判断路径是否为目录；对象存储中的目录可能只能通过带 '/' 的路径识别
+    pub async fn is_dir_path(&self, path: &str) -> Result<bool> {
+        let normalized = normalize_path(path);
+        if normalized.is_empty() || normalized.ends_with('/') {
+            return Ok(true);
+        }
+
+        match self.operator.stat(&normalized).await {
+            Ok(meta) => Ok(meta.is_dir()),
+            Err(e) if e.kind() == opendal::ErrorKind::NotFound => {
+                match self.operator.stat(&format!("{}/", normalized)).await {
+                    Ok(meta) => Ok(meta.is_dir()),
+                    Err(_) => Err(e.into()),
+                }
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// 获取文件元数据
     pub async fn stat(&self, path: &str) -> Result<Metadata> {
         debug!("获取文件元数据: {}", path);
@@ -402,6 +1048,7 @@ impl FileManager {
             // 读取文件内容
             match self.operator.read(&normalized_path).await {
                 Ok(data) => {
+                    self.throttle.consume(data.len() as u64).await;
                     // 生成ZIP内的文件名（去掉前导斜杠）
                     let archive_name = file_path.strip_prefix('/').unwrap_or(file_path);
 
@@ -428,105 +1075,201 @@ impl FileManager {
         Ok(())
     }
 
-    /// 递归列出目录下的所有文件（不包括目录）- 使用迭代方式避免递归问题
+    /// 以流的方式遍历目录树，支持深度限制、包含/排除规则与条目上限
+    pub fn walk(&self, path: &str, options: WalkOptions) -> BoxStream<'static, Result<WalkEvent>> {
+        walker::walk(self.operator.clone(), &normalize_path(path), options)
+    }
+
+    /// 递归列出目录下的所有文件（不包括目录），结果按路径排序
     pub async fn list_files_recursive(&self, path: &str) -> Result<Vec<String>> {
         debug!("递归列出目录下所有文件: {}", path);
 
-        let mut all_files = std::collections::HashSet::new(); // 使用HashSet防止重复
-        let mut dirs_to_visit = vec![path.to_string()];
-        let mut visited_dirs = std::collections::HashSet::new();
-        let max_items = 1000; // 防止无限制地收集文件
+        let mut files: Vec<String> = self
+            .walk(path, WalkOptions::new())
+            .try_filter_map(|event| async move {
+                Ok(match event {
+                    WalkEvent::Entry(entry) if !entry.is_dir() => Some(entry.path.clone()),
+                    _ => None,
+                })
+            })
+            .try_collect()
+            .await?;
+        files.sort();
 
-        while let Some(current_dir) = dirs_to_visit.pop() {
-            // 规范化当前目录路径，确保一致性
-            let normalized_current_dir = if current_dir.starts_with('/') {
-                current_dir.clone()
-            } else {
-                format!("/{}", current_dir)
-            };
+        info!("递归找到 {} 个文件", files.len());
+        Ok(files)
+    }
+}
 
-            // 防止无限循环
-            if visited_dirs.contains(&normalized_current_dir) {
-                debug!("跳过已访问的目录: {}", normalized_current_dir);
-                continue;
+/// 将远程的相对路径映射到本地目录下，拒绝 `..`、`.`、空段与绝对路径，防止写到目录之外
+fn local_path_for(local_dir: &Path, relative: &str) -> Result<PathBuf> {
+    let relative = relative.strip_suffix('/').unwrap_or(relative);
+    let mut local_path = local_dir.to_path_buf();
+    for part in relative.split('/') {
+        let mut components = Path::new(part).components();
+        match (components.next(), components.next()) {
+            (Some(std::path::Component::Normal(name)), None) if name == part => {
+                local_path.push(name)
+            }
+            _ => {
+                return Err(Error::new_other(&format!(
+                    "远程路径包含不安全的片段: {}",
+                    relative
+                )))
             }
-            visited_dirs.insert(normalized_current_dir.clone());
+        }
+    }
+    Ok(local_path)
+}
 
-            // 限制文件数量，防止内存溢出
-            if all_files.len() >= max_items {
-                debug!("达到最大文件数量限制 {}, 停止递归", max_items);
-                break;
+/// 在两个 Operator 之间分块流式复制文件，不经过本地临时文件，返回复制的字节数
+pub(crate) async fn stream_copy<F>(
+    source: &Operator,
+    from: &str,
+    target: &Operator,
+    to: &str,
+    chunk_size: usize,
+    throttle: &Throttle,
+    mut on_chunk: F,
+) -> Result<u64>
+where
+    F: FnMut(u64) + Send,
+{
+    let mut stream = source
+        .reader_with(from)
+        .chunk(chunk_size)
+        .await?
+        .into_stream(..)
+        .await?;
+    let mut writer = target.writer_with(to).chunk(chunk_size).await?;
+
+    let slice_size = throttle.slice_size(chunk_size);
+    let mut copied = 0u64;
+    loop {
+        let buffer = match stream.try_next().await {
+            Ok(Some(buffer)) => buffer,
+            Ok(None) => break,
+            Err(e) => {
+                let _ = writer.abort().await;
+                return Err(e.into());
+            }
+        };
+        for bytes in buffer {
+            let mut offset = 0;
+            while offset < bytes.len() {
+                let slice = bytes.slice(offset..bytes.len().min(offset + slice_size));
+                let len = slice.len() as u64;
+                throttle.consume(len).await;
+                if let Err(e) = writer.write(slice).await {
+                    let _ = writer.abort().await;
+                    return Err(e.into());
+                }
+                offset += len as usize;
+                copied += len;
+                on_chunk(len);
             }
+        }
+    }
 
-            let normalized_path = normalize_path(&current_dir);
+    if let Err(e) = writer.close().await {
+        let _ = writer.abort().await;
+        return Err(e.into());
+    }
+    Ok(copied)
+}
 
-            // 确保路径以 / 结尾（对于目录）
-            let list_path = if normalized_path.is_empty() {
-                "".to_string()
-            } else if normalized_path.ends_with('/') {
-                normalized_path
-            } else {
-                format!("{}/", normalized_path)
-            };
+fn transfer_result(source: &str, target: &str, result: Result<u64>) -> TransferResult {
+    let (size, error) = match result {
+        Ok(size) => (size, None),
+        Err(e) => (0, Some(e.to_string())),
+    };
+    TransferResult {
+        source: format!("/{}", source.trim_start_matches('/')),
+        target: format!("/{}", target.trim_start_matches('/')),
+        size,
+        error,
+    }
+}
 
-            debug!("处理目录: {} (原始路径: {})", list_path, current_dir);
-
-            // 获取目录内容
-            match self.operator.list(&list_path).await {
-                Ok(entries) => {
-                    debug!("目录 {} 包含 {} 个条目", list_path, entries.len());
-
-                    for entry in entries {
-                        let entry_name = entry.name();
-                        let entry_path = entry.path();
-
-                        // 跳过特殊目录
-                        if entry_name == "." || entry_name == ".." {
-                            continue;
-                        }
-
-                        if entry.metadata().is_dir() {
-                            // 如果是目录，添加到待访问列表
-                            let sub_dir_path = if entry_path.starts_with('/') {
-                                entry_path.to_string()
-                            } else {
-                                format!("/{}", entry_path)
-                            };
-
-                            debug!("发现子目录: {}", sub_dir_path);
-
-                            // 防止重复添加目录，使用规范化的路径进行比较
-                            if !visited_dirs.contains(&sub_dir_path) {
-                                dirs_to_visit.push(sub_dir_path);
-                            }
-                        } else {
-                            // 添加文件到结果，使用规范化的路径
-                            let file_path = if entry_path.starts_with('/') {
-                                entry_path.to_string()
-                            } else {
-                                format!("/{}", entry_path)
-                            };
-
-                            debug!("找到文件: {}", file_path);
-                            // 使用HashSet自动去重
-                            all_files.insert(file_path);
-                        }
-                    }
-                }
-                Err(e) => {
-                    debug!("无法列出目录 {}: {}", list_path, e);
-                    // 继续处理其他目录
-                    continue;
+/// 本地目录树：子目录与文件的相对路径（使用 '/' 分隔）
+struct LocalTree {
+    dirs: Vec<String>,
+    files: Vec<(std::path::PathBuf, String)>,
+}
+
+/// 遍历本地目录，父目录排在子目录之前
+fn walk_local_dir(root: &Path) -> Result<LocalTree> {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|e| Error::new_io(&e.to_string()))?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+
+            // 不跟随指向目录的符号链接，避免链接指回上层目录时无限遍历
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                dirs.push(relative);
+                pending.push(path);
+            } else if file_type.is_symlink() {
+                match std::fs::metadata(&path) {
+                    Ok(meta) if meta.is_file() => files.push((path, relative)),
+                    Ok(_) => warn!("跳过指向目录的符号链接: {}", path.display()),
+                    Err(e) => warn!("跳过无效的符号链接: {}: {}", path.display(), e),
                 }
+            } else {
+                files.push((path, relative));
             }
         }
+    }
 
-        // 将HashSet转换为Vec并排序
-        let mut result: Vec<String> = all_files.into_iter().collect();
-        result.sort();
+    dirs.sort();
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(LocalTree { dirs, files })
+}
 
-        info!("递归找到 {} 个文件", result.len());
-        Ok(result)
+/// 计算本地目录中会被上传的文件总大小，遍历规则与目录上传相同
+pub fn local_dir_size(local_dir: &Path) -> Result<u64> {
+    Ok(walk_local_dir(local_dir)?
+        .files
+        .iter()
+        .filter_map(|(path, _)| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum())
+}
+
+/// 计算续传起始偏移量：本地部分文件通过校验时返回已下载的字节数，否则返回 0 重新下载
+fn resume_offset(local_path: &Path, remote_state: &DownloadCheckpoint) -> u64 {
+    let local_len = match std::fs::metadata(local_path) {
+        Ok(meta) if meta.is_file() => meta.len(),
+        _ => return 0,
+    };
+
+    if local_len > remote_state.content_length {
+        info!("本地文件大于远程文件，重新下载: {}", local_path.display());
+        return 0;
+    }
+
+    match DownloadCheckpoint::load(local_path) {
+        Some(recorded) if recorded.matches(remote_state) => local_len,
+        Some(_) => {
+            info!("远程文件已变化，重新下载: {}", local_path.display());
+            0
+        }
+        // 没有断点记录时无法确认本地文件是此前中断的下载，可能只是同名的其他文件
+        None => {
+            info!("没有断点记录，重新下载: {}", local_path.display());
+            0
+        }
     }
 }
 
@@ -671,6 +1414,23 @@ mod tests {
         assert_eq!(files.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_list_files_recursive_no_cap() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator);
+
+        for i in 0..1005 {
+            file_manager
+                .operator
+                .write(&format!("many/{}/f{}.txt", i % 3, i), "x")
+                .await
+                .unwrap();
+        }
+
+        let files = file_manager.list_files_recursive("/many").await.unwrap();
+        assert_eq!(files.len(), 1005);
+    }
+
     #[tokio::test]
     async fn test_batch_download_single_file() {
         let (operator, _temp_dir) = create_test_operator().await;
@@ -749,6 +1509,493 @@ mod tests {
         assert!(metadata.len() > 0);
     }
 
+    #[tokio::test]
+    async fn test_upload_download_streaming_roundtrip() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        // 使用很小的数据块，确保走多块读写路径
+        let file_manager = FileManager::new(operator).with_chunk_size(1024);
+
+        let local_dir = TempDir::new().unwrap();
+        let source = local_dir.path().join("source.bin");
+        let content: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&source, &content).unwrap();
+
+        file_manager
+            .upload(&source, "/uploads/source.bin")
+            .await
+            .unwrap();
+
+        let target = local_dir.path().join("nested/target.bin");
+        file_manager
+            .download("/uploads/source.bin", &target)
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&target).unwrap(), content);
+    }
+
+    #[tokio::test]
+    async fn test_download_with_progress_reports_each_chunk() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator).with_chunk_size(1024);
+
+        file_manager
+            .operator
+            .write("big.bin", vec![7u8; 4096])
+            .await
+            .unwrap();
+
+        let local_dir = TempDir::new().unwrap();
+        let target = local_dir.path().join("big.bin");
+        let mut reports = Vec::new();
+        file_manager
+            .download_with_progress("/big.bin", &target, |progress| {
+                reports.push((progress.transferred, progress.total))
+            })
+            .await
+            .unwrap();
+
+        assert!(reports.len() > 1);
+        assert_eq!(reports.last(), Some(&(4096, 4096)));
+    }
+
+    #[tokio::test]
+    async fn test_upload_with_progress_reports_each_chunk() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator).with_chunk_size(1024);
+
+        let local_dir = TempDir::new().unwrap();
+        let source = local_dir.path().join("big.bin");
+        std::fs::write(&source, vec![1u8; 3000]).unwrap();
+
+        let mut reports = Vec::new();
+        file_manager
+            .upload_with_progress(&source, "/big.bin", |progress| {
+                assert_eq!(progress.direction, TransferDirection::Upload);
+                reports.push(progress.transferred)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(reports, vec![1024, 2048, 3000]);
+    }
+
+    #[tokio::test]
+    async fn test_upload_respects_rate_limit() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator)
+            .with_chunk_size(10_000)
+            .with_rate_limiter(Arc::new(RateLimiter::new(40_000)));
+
+        let local_dir = TempDir::new().unwrap();
+        let source = local_dir.path().join("limited.bin");
+        std::fs::write(&source, vec![3u8; 60_000]).unwrap();
+
+        // 首秒的突发额度用完后，剩余 20000 字节需要约 0.5 秒
+        let start = std::time::Instant::now();
+        file_manager.upload(&source, "/limited.bin").await.unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_millis(450));
+    }
+
+    #[tokio::test]
+    async fn test_resume_download_continues_partial_file() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator).with_chunk_size(1024);
+
+        let content: Vec<u8> = (0..5000u32).map(|i| (i % 13) as u8).collect();
+        file_manager
+            .operator
+            .write("data.bin", content.clone())
+            .await
+            .unwrap();
+
+        // 模拟中断的下载：本地只有前 2000 字节，并留有断点记录
+        let local_dir = TempDir::new().unwrap();
+        let target = local_dir.path().join("data.bin");
+        std::fs::write(&target, &content[..2000]).unwrap();
+        let meta = file_manager.stat("/data.bin").await.unwrap();
+        DownloadCheckpoint::from_metadata(&meta)
+            .save(&target)
+            .unwrap();
+
+        let mut first_report = None;
+        file_manager
+            .resume_download_with_progress("/data.bin", &target, |progress| {
+                first_report.get_or_insert(progress.transferred);
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(first_report, Some(3024));
+        assert_eq!(std::fs::read(&target).unwrap(), content);
+        assert!(!DownloadCheckpoint::path_for(&target).exists());
+    }
+
+    #[tokio::test]
+    async fn test_resume_download_restarts_when_remote_changed() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator);
+
+        file_manager
+            .operator
+            .write("data.txt", "new remote content")
+            .await
+            .unwrap();
+
+        let local_dir = TempDir::new().unwrap();
+        let target = local_dir.path().join("data.txt");
+        std::fs::write(&target, "old").unwrap();
+        DownloadCheckpoint {
+            etag: None,
+            content_length: 42,
+            last_modified: None,
+        }
+        .save(&target)
+        .unwrap();
+
+        file_manager
+            .resume_download_with_progress("/data.txt", &target, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&target).unwrap(), b"new remote content");
+    }
+
+    #[tokio::test]
+    async fn test_resume_download_without_checkpoint_restarts() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator);
+
+        file_manager
+            .operator
+            .write("data.txt", "remote content")
+            .await
+            .unwrap();
+
+        // 同名但无关的较小本地文件不能被当作已下载的部分
+        let local_dir = TempDir::new().unwrap();
+        let target = local_dir.path().join("data.txt");
+        std::fs::write(&target, "local").unwrap();
+
+        file_manager
+            .resume_download_with_progress("/data.txt", &target, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&target).unwrap(), b"remote content");
+    }
+
+    #[tokio::test]
+    async fn test_upload_dir_recreates_tree() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator);
+
+        let local_dir = TempDir::new().unwrap();
+        let root = local_dir.path();
+        std::fs::create_dir_all(root.join("sub/deeper")).unwrap();
+        std::fs::create_dir_all(root.join("empty")).unwrap();
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        std::fs::write(root.join("sub/b.txt"), "bb").unwrap();
+        std::fs::write(root.join("sub/deeper/c.txt"), "ccc").unwrap();
+
+        let mut completed = Vec::new();
+        let report = file_manager
+            .upload_dir(root, "/backup/", 2, |progress| {
+                completed.push((progress.completed, progress.total))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(report.results.len(), 3);
+        assert_eq!(report.failed().count(), 0);
+        assert_eq!(report.transferred_bytes(), 6);
+        assert_eq!(completed.last(), Some(&(3, 3)));
+
+        let files = file_manager.list_files_recursive("/backup").await.unwrap();
+        assert_eq!(
+            files,
+            vec![
+                "/backup/a.txt",
+                "/backup/sub/b.txt",
+                "/backup/sub/deeper/c.txt"
+            ]
+        );
+        assert!(file_manager.stat("/backup/empty/").await.unwrap().is_dir());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_upload_dir_skips_symlinked_dirs() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator);
+
+        let local_dir = TempDir::new().unwrap();
+        let root = local_dir.path();
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+        std::fs::write(root.join("a.txt"), "a").unwrap();
+        // 指回根目录的链接会形成循环，指向文件的链接按文件上传
+        std::os::unix::fs::symlink(root, root.join("sub/loop")).unwrap();
+        std::os::unix::fs::symlink(root.join("a.txt"), root.join("sub/link.txt")).unwrap();
+        std::os::unix::fs::symlink(root.join("missing"), root.join("broken")).unwrap();
+
+        let report = file_manager
+            .upload_dir(root, "/backup", 2, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(report.failed().count(), 0);
+        let files = file_manager.list_files_recursive("/backup").await.unwrap();
+        assert_eq!(files, vec!["/backup/a.txt", "/backup/sub/link.txt"]);
+        assert_eq!(local_dir_size(root).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_download_dir_mirrors_tree() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator.clone());
+        operator.write("remote/a.txt", "a").await.unwrap();
+        operator.write("remote/sub/b.txt", "bb").await.unwrap();
+        operator.write("other.txt", "x").await.unwrap();
+        operator.create_dir("remote/empty/").await.unwrap();
+
+        let local_dir = TempDir::new().unwrap();
+        let target = local_dir.path().join("mirror");
+        let report = file_manager
+            .download_dir("/remote", &target, 2, |_| {})
+            .await
+            .unwrap();
+
+        assert_eq!(report.results.len(), 2);
+        assert_eq!(report.failed().count(), 0);
+        assert_eq!(std::fs::read_to_string(target.join("a.txt")).unwrap(), "a");
+        assert_eq!(
+            std::fs::read_to_string(target.join("sub").join("b.txt")).unwrap(),
+            "bb"
+        );
+        assert!(!target.join("other.txt").exists());
+        assert!(target.join("empty").is_dir());
+
+        let remote_modified = operator
+            .stat("remote/sub/b.txt")
+            .await
+            .unwrap()
+            .last_modified()
+            .unwrap();
+        let local_modified = std::fs::metadata(target.join("sub").join("b.txt"))
+            .unwrap()
+            .modified()
+            .unwrap();
+        assert_eq!(
+            chrono::DateTime::<chrono::Utc>::from(local_modified).timestamp(),
+            remote_modified.timestamp()
+        );
+    }
+
+    #[test]
+    fn test_local_path_for_rejects_unsafe_keys() {
+        let local_dir = Path::new("/tmp/mirror");
+        assert_eq!(
+            local_path_for(local_dir, "sub/b.txt").unwrap(),
+            local_dir.join("sub").join("b.txt")
+        );
+        assert_eq!(
+            local_path_for(local_dir, "empty/").unwrap(),
+            local_dir.join("empty")
+        );
+
+        for relative in [
+            "../evil.txt",
+            "sub/../../evil.txt",
+            "./a.txt",
+            "a//b.txt",
+            "/etc/passwd",
+            "",
+        ] {
+            assert!(
+                local_path_for(local_dir, relative).is_err(),
+                "应该拒绝: {}",
+                relative
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_recursive() {
+        for batch in [false, true] {
+            let (operator, _temp_dir) = create_test_operator().await;
+            let file_manager = FileManager::new(operator.clone())
+                .with_capabilities(Capabilities::default().with_batch_delete(batch));
+            operator.write("tree/a.txt", "a").await.unwrap();
+            operator.write("tree/sub/b.txt", "b").await.unwrap();
+            operator.create_dir("tree/sub/empty/").await.unwrap();
+            operator.write("keep.txt", "k").await.unwrap();
+
+            file_manager.delete_recursive("/tree").await.unwrap();
+
+            assert!(!operator.exists("tree/").await.unwrap());
+            assert!(!operator.exists("tree/sub/b.txt").await.unwrap());
+            assert!(operator.exists("keep.txt").await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_recursive_rejects_root() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator);
+
+        assert!(file_manager.delete_recursive("/").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_and_rename_directory() {
+        for native in [false, true] {
+            let (operator, _temp_dir) = create_test_operator().await;
+            let file_manager = FileManager::new(operator.clone()).with_capabilities(
+                Capabilities::default()
+                    .with_copy(native)
+                    .with_rename(native),
+            );
+            setup_test_files(&operator).await.unwrap();
+
+            file_manager.copy("/dir1", "/copied").await.unwrap();
+            assert_eq!(
+                operator
+                    .read("copied/subdir1/file4.txt")
+                    .await
+                    .unwrap()
+                    .to_vec(),
+                b"content of file4"
+            );
+            assert!(operator.exists("dir1/file3.txt").await.unwrap());
+
+            file_manager.rename("/copied", "/moved").await.unwrap();
+            assert!(operator.exists("moved/file3.txt").await.unwrap());
+            assert!(!operator.exists("copied/").await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_into_directory() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator.clone());
+        setup_test_files(&operator).await.unwrap();
+
+        file_manager.copy("/file1.txt", "/dir2/").await.unwrap();
+        file_manager
+            .rename("/file2.txt", "/dir2/renamed.txt")
+            .await
+            .unwrap();
+
+        assert!(operator.exists("dir2/file1.txt").await.unwrap());
+        assert!(operator.exists("dir2/renamed.txt").await.unwrap());
+        assert!(!operator.exists("file2.txt").await.unwrap());
+        assert!(file_manager.copy("/dir1", "/dir1/sub").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_file_onto_itself_is_rejected() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        // 默认能力不支持原生复制，会走读写复制的路径
+        let file_manager = FileManager::new(operator.clone());
+        setup_test_files(&operator).await.unwrap();
+
+        assert!(file_manager.copy("/file1.txt", "/file1.txt").await.is_err());
+        assert!(file_manager.copy("/file1.txt", "/").await.is_err());
+        assert!(file_manager
+            .rename("/dir1/file3.txt", "/dir1")
+            .await
+            .is_err());
+        assert_eq!(
+            operator.read("file1.txt").await.unwrap().to_vec(),
+            b"content of file1"
+        );
+        assert_eq!(
+            operator.read("dir1/file3.txt").await.unwrap().to_vec(),
+            b"content of file3"
+        );
+
+        // 目标是已存在的目录但没有以 '/' 结尾时，同样放到该目录下
+        file_manager.copy("/file1.txt", "/dir2").await.unwrap();
+        assert_eq!(
+            operator.read("dir2/file1.txt").await.unwrap().to_vec(),
+            b"content of file1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transfer_to_other_connection() {
+        let (source_op, _source_dir) = create_test_operator().await;
+        let (target_op, _target_dir) = create_test_operator().await;
+        setup_test_files(&source_op).await.unwrap();
+        let source = FileManager::new(source_op).with_chunk_size(4);
+        let target = FileManager::new(target_op.clone());
+
+        let mut chunks = 0;
+        let report = source
+            .transfer_to("/dir1", &target, "/backup", |progress| {
+                assert_eq!(progress.direction, TransferDirection::Copy);
+                chunks += 1;
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(report.results.len(), 2);
+        assert_eq!(report.failed().count(), 0);
+        assert!(chunks >= 8);
+        assert_eq!(
+            target_op
+                .read("backup/subdir1/file4.txt")
+                .await
+                .unwrap()
+                .to_vec(),
+            b"content of file4"
+        );
+
+        let report = source
+            .transfer_to("/file1.txt", &target, "/", |_| {})
+            .await
+            .unwrap();
+        assert_eq!(report.results[0].target, "/file1.txt");
+        assert!(target_op.exists("file1.txt").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_move_to_other_connection() {
+        let (source_op, _source_dir) = create_test_operator().await;
+        let (target_op, _target_dir) = create_test_operator().await;
+        setup_test_files(&source_op).await.unwrap();
+        let source = FileManager::new(source_op.clone());
+        let target = FileManager::new(target_op.clone());
+
+        let report = source
+            .move_to("/dir1", &target, "/moved", |_| {})
+            .await
+            .unwrap();
+        assert_eq!(report.failed().count(), 0);
+        assert!(target_op.exists("moved/subdir1/file4.txt").await.unwrap());
+        assert!(!source_op.exists("dir1/file3.txt").await.unwrap());
+
+        source
+            .move_to("/file1.txt", &target, "/", |_| {})
+            .await
+            .unwrap();
+        assert!(target_op.exists("file1.txt").await.unwrap());
+        assert!(!source_op.exists("file1.txt").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_download_missing_file() {
+        let (operator, _temp_dir) = create_test_operator().await;
+        let file_manager = FileManager::new(operator);
+
+        let local_dir = TempDir::new().unwrap();
+        let result = file_manager
+            .download("/missing.txt", &local_dir.path().join("missing.txt"))
+            .await;
+
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_normalize_path() {
         // 测试路径规范化函数
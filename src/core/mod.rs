@@ -1,12 +1,20 @@
 pub mod config;
 pub mod error;
 pub mod file;
+pub mod sync;
+pub mod throttle;
+pub mod transfer;
+pub mod walker;
 
 pub use config::{ConnectionConfig, ConnectionManager};
-pub use error::{Error, Result};
+pub use error::{Error, ErrorKind, Result};
 pub use file::FileManager;
+pub use transfer::{TransferDirection, TransferProgress};
 
 pub mod operator {
+    use std::sync::Arc;
+
+    use crate::core::throttle::RateLimiter;
     use crate::core::Result;
     use crate::protocols::Protocol;
     use opendal::Operator;
@@ -16,9 +24,14 @@ pub mod operator {
         protocol.create_operator()
     }
 
-    /// 根据协议创建文件管理器
+    /// 根据协议创建文件管理器，连接配置了带宽限制时一并生效
     pub fn create_file_manager(protocol: &dyn Protocol) -> Result<super::FileManager> {
         let operator = create_operator(protocol)?;
-        Ok(super::FileManager::new(operator))
+        let mut file_manager =
+            super::FileManager::new(operator).with_capabilities(protocol.get_capabilities());
+        if let Some(rate) = protocol.get_rate_limit() {
+            file_manager = file_manager.with_rate_limiter(Arc::new(RateLimiter::new(rate)));
+        }
+        Ok(file_manager)
     }
 }
@@ -0,0 +1,465 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use log::{debug, info};
+use opendal::Scheme;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::Result;
+use crate::core::file::FileManager;
+use crate::core::transfer::{TransferProgress, DEFAULT_TRANSFER_CONCURRENCY};
+use crate::core::walker::{WalkEntry, WalkOptions};
+
+/// 同步时用于比较的文件状态
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileState {
+    pub size: u64,
+    pub modified: Option<DateTime<Utc>>,
+    pub etag: Option<String>,
+    /// 文件所在存储的协议，不同协议的 ETag 不可比较
+    pub scheme: Scheme,
+}
+
+impl FileState {
+    /// 判断目标文件是否需要用源文件更新
+    ///
+    /// 大小不同时需要更新；双方位于同一协议且都有内容 ETag 时以 ETag 为准；
+    /// 否则源文件更新时间晚于目标时更新。
+    pub fn differs_from(&self, target: &FileState) -> bool {
+        if self.size != target.size {
+            return true;
+        }
+        if self.scheme == target.scheme {
+            if let (Some(source), Some(target)) = (self.content_etag(), target.content_etag()) {
+                return source != target;
+            }
+        }
+        match (self.modified, target.modified) {
+            (Some(source), Some(target)) => source > target,
+            _ => false,
+        }
+    }
+
+    /// 能反映文件内容的 ETag；分片上传的 ETag（以 `-分片数` 结尾）与内容无直接对应，不参与比较
+    fn content_etag(&self) -> Option<&str> {
+        let etag = self.etag.as_deref()?;
+        let is_multipart = etag
+            .trim_matches('"')
+            .rsplit_once('-')
+            .is_some_and(|(_, parts)| {
+                !parts.is_empty() && parts.bytes().all(|b| b.is_ascii_digit())
+            });
+        (!is_multipart).then_some(etag)
+    }
+}
+
+/// 同步动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncAction {
+    /// 目标端不存在，需要创建
+    Create,
+    /// 目标端已存在但内容不同，需要覆盖
+    Update,
+    /// 源端已不存在，需要从目标端删除
+    Delete,
+}
+
+/// 同步计划中的一项，路径相对于同步根目录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncItem {
+    pub action: SyncAction,
+    pub path: String,
+    pub size: u64,
+}
+
+/// 同步计划
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncPlan {
+    pub items: Vec<SyncItem>,
+}
+
+impl SyncPlan {
+    /// 比较源端与目标端的文件树，生成同步计划
+    pub fn build(
+        source: &BTreeMap<String, FileState>,
+        target: &BTreeMap<String, FileState>,
+        delete_extraneous: bool,
+    ) -> Self {
+        let mut items = Vec::new();
+
+        for (path, state) in source {
+            let action = match target.get(path) {
+                None => SyncAction::Create,
+                Some(existing) if state.differs_from(existing) => SyncAction::Update,
+                Some(_) => continue,
+            };
+            items.push(SyncItem {
+                action,
+                path: path.clone(),
+                size: state.size,
+            });
+        }
+
+        if delete_extraneous {
+            for (path, state) in target {
+                if !source.contains_key(path) {
+                    items.push(SyncItem {
+                        action: SyncAction::Delete,
+                        path: path.clone(),
+                        size: state.size,
+                    });
+                }
+            }
+        }
+
+        Self { items }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// 需要传输的总字节数
+    pub fn transfer_bytes(&self) -> u64 {
+        self.items
+            .iter()
+            .filter(|item| item.action != SyncAction::Delete)
+            .map(|item| item.size)
+            .sum()
+    }
+}
+
+/// 同步计划中单项的执行结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncResult {
+    pub item: SyncItem,
+    pub error: Option<String>,
+}
+
+/// 同步执行报告
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub results: Vec<SyncResult>,
+}
+
+impl SyncReport {
+    /// 执行失败的项
+    pub fn failed(&self) -> impl Iterator<Item = &SyncResult> {
+        self.results.iter().filter(|r| r.error.is_some())
+    }
+
+    /// 成功传输的字节数
+    pub fn transferred_bytes(&self) -> u64 {
+        self.results
+            .iter()
+            .filter(|r| r.error.is_none() && r.item.action != SyncAction::Delete)
+            .map(|r| r.item.size)
+            .sum()
+    }
+
+    /// 成功执行的指定动作数量
+    pub fn count(&self, action: SyncAction) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.error.is_none() && r.item.action == action)
+            .count()
+    }
+
+    /// 汇总传输字节数与各类动作的数量
+    pub fn summary(&self) -> SyncSummary {
+        SyncSummary {
+            created: self.count(SyncAction::Create),
+            updated: self.count(SyncAction::Update),
+            deleted: self.count(SyncAction::Delete),
+            failed: self.failed().count(),
+            bytes_transferred: self.transferred_bytes(),
+        }
+    }
+}
+
+/// 同步结果汇总
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub failed: usize,
+    pub bytes_transferred: u64,
+}
+
+/// 扫描目录树下所有文件的状态，键为相对于 `root` 的路径
+///
+/// 优先使用列表结果中的元数据，只有列表未返回修改时间的协议（如本地文件系统）才逐个 stat。
+pub async fn scan(file_manager: &FileManager, root: &str) -> Result<BTreeMap<String, FileState>> {
+    debug!("扫描同步目录: {}", root);
+
+    let scheme = file_manager.scheme();
+    let entries: Vec<WalkEntry> = file_manager
+        .walk(&root_prefix(root), WalkOptions::new())
+        // 遗漏子目录会让 --delete 误删目标端的文件，列出失败时中止同步
+        .try_filter_map(|event| async move {
+            Ok(event
+                .into_entry()?
+                .filter(|entry| !entry.is_dir())
+                .map(|entry| *entry))
+        })
+        .try_collect()
+        .await?;
+
+    let states: Vec<(String, FileState)> = futures::stream::iter(entries)
+        .map(|entry| async move {
+            let meta = match entry.metadata.last_modified() {
+                Some(_) => entry.metadata,
+                None => file_manager.stat(&entry.path).await?,
+            };
+            Ok::<_, crate::core::error::Error>((
+                entry.relative,
+                FileState {
+                    size: meta.content_length(),
+                    modified: meta.last_modified(),
+                    etag: meta.etag().map(|s| s.to_string()),
+                    scheme,
+                },
+            ))
+        })
+        .buffer_unordered(DEFAULT_TRANSFER_CONCURRENCY)
+        .try_collect()
+        .await?;
+
+    info!("扫描到 {} 个文件: {}", states.len(), root);
+    Ok(states.into_iter().collect())
+}
+
+/// 按计划将源端的变更并发同步到目标端，单项失败不会中断其余项
+///
+/// 先执行新建和更新，全部完成后再执行删除，避免中途失败时目标端先丢失文件。
+pub async fn execute<F>(
+    source: &FileManager,
+    source_root: &str,
+    target: &FileManager,
+    target_root: &str,
+    plan: &SyncPlan,
+    concurrency: usize,
+    progress_callback: F,
+) -> SyncReport
+where
+    F: FnMut(&TransferProgress) + Send + Sync,
+{
+    let source_prefix = root_prefix(source_root);
+    let target_prefix = root_prefix(target_root);
+    let progress_callback = Mutex::new(progress_callback);
+    let mut report = SyncReport::default();
+
+    let (deletes, transfers): (Vec<&SyncItem>, Vec<&SyncItem>) = plan
+        .items
+        .iter()
+        .partition(|item| item.action == SyncAction::Delete);
+
+    for batch in [transfers, deletes] {
+        let mut results = futures::stream::iter(batch)
+            .map(|item| {
+                let from = format!("{}{}", source_prefix, item.path);
+                let to = format!("{}{}", target_prefix, item.path);
+                let progress_callback = &progress_callback;
+                async move {
+                    debug!("同步 {:?}: {} -> {}", item.action, from, to);
+                    let result = match item.action {
+                        SyncAction::Create | SyncAction::Update => source
+                            .transfer_file_to(&from, target, &to, &mut |progress| {
+                                if let Ok(mut callback) = progress_callback.lock() {
+                                    callback(progress);
+                                }
+                            })
+                            .await
+                            .map(|_| ()),
+                        SyncAction::Delete => target.delete(&to).await,
+                    };
+                    SyncResult {
+                        item: item.clone(),
+                        error: result.err().map(|e| e.to_string()),
+                    }
+                }
+            })
+            .buffer_unordered(concurrency.max(1));
+
+        while let Some(result) = results.next().await {
+            report.results.push(result);
+        }
+    }
+
+    let summary = report.summary();
+    info!(
+        "同步完成: 新建 {} 个，更新 {} 个，删除 {} 个，失败 {} 个，传输 {} 字节",
+        summary.created,
+        summary.updated,
+        summary.deleted,
+        summary.failed,
+        summary.bytes_transferred
+    );
+    report
+}
+
+/// 同步根目录的规范前缀，以 '/' 开头和结尾
+fn root_prefix(root: &str) -> String {
+    let trimmed = root.trim_matches('/');
+    if trimmed.is_empty() {
+        "/".to_string()
+    } else {
+        format!("/{}/", trimmed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opendal::services::Fs;
+    use opendal::Operator;
+    use tempfile::TempDir;
+
+    fn state(size: u64, modified: i64, etag: Option<&str>) -> FileState {
+        FileState {
+            size,
+            modified: DateTime::from_timestamp(modified, 0),
+            etag: etag.map(|s| s.to_string()),
+            scheme: Scheme::S3,
+        }
+    }
+
+    fn create_file_manager() -> (FileManager, Operator, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let builder = Fs::default().root(temp_dir.path().to_str().unwrap());
+        let operator = Operator::new(builder).unwrap().finish();
+        (FileManager::new(operator.clone()), operator, temp_dir)
+    }
+
+    #[test]
+    fn test_file_state_differs() {
+        // 大小不同
+        assert!(state(1, 0, None).differs_from(&state(2, 0, None)));
+        // ETag 优先于修改时间
+        assert!(!state(1, 100, Some("a")).differs_from(&state(1, 0, Some("a"))));
+        assert!(state(1, 0, Some("a")).differs_from(&state(1, 100, Some("b"))));
+        // 无 ETag 时源端较新才更新
+        assert!(state(1, 100, None).differs_from(&state(1, 0, None)));
+        assert!(!state(1, 0, None).differs_from(&state(1, 100, None)));
+    }
+
+    #[test]
+    fn test_file_state_ignores_incomparable_etags() {
+        // 不同协议的 ETag 不可比较，按修改时间判断
+        let mut azure = state(1, 100, Some("\"0x8DC\""));
+        azure.scheme = Scheme::Azblob;
+        assert!(!state(1, 0, Some("\"abc\"")).differs_from(&azure));
+        assert!(state(1, 200, Some("\"abc\"")).differs_from(&azure));
+
+        // 分片上传的 ETag 与单次上传的 ETag 不可比较
+        let multipart = state(1, 0, Some("\"9b2cf535f27731c974343645a3985328-3\""));
+        assert!(!multipart.differs_from(&state(1, 100, Some("\"abc\""))));
+        assert!(!state(1, 0, Some("\"abc\"")).differs_from(&multipart));
+        assert!(state(1, 100, Some("\"abc\"")).differs_from(&multipart));
+    }
+
+    #[test]
+    fn test_build_plan() {
+        let source = BTreeMap::from([
+            ("new.txt".to_string(), state(1, 0, None)),
+            ("changed.txt".to_string(), state(2, 0, None)),
+            ("same.txt".to_string(), state(3, 0, None)),
+        ]);
+        let target = BTreeMap::from([
+            ("changed.txt".to_string(), state(1, 0, None)),
+            ("same.txt".to_string(), state(3, 0, None)),
+            ("extra.txt".to_string(), state(4, 0, None)),
+        ]);
+
+        let plan = SyncPlan::build(&source, &target, false);
+        let actions: Vec<_> = plan
+            .items
+            .iter()
+            .map(|i| (i.action, i.path.as_str()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (SyncAction::Update, "changed.txt"),
+                (SyncAction::Create, "new.txt")
+            ]
+        );
+        assert_eq!(plan.transfer_bytes(), 3);
+
+        let plan = SyncPlan::build(&source, &target, true);
+        assert_eq!(plan.items.len(), 3);
+        assert_eq!(plan.items[2].action, SyncAction::Delete);
+        assert_eq!(plan.items[2].path, "extra.txt");
+    }
+
+    #[tokio::test]
+    async fn test_scan_and_execute() {
+        let (source, source_op, _source_dir) = create_file_manager();
+        let (target, target_op, _target_dir) = create_file_manager();
+        source_op.write("data/a.txt", "aaa").await.unwrap();
+        source_op.write("data/sub/b.txt", "b").await.unwrap();
+        target_op.write("mirror/stale.txt", "old").await.unwrap();
+
+        let source_tree = scan(&source, "/data").await.unwrap();
+        assert_eq!(
+            source_tree.keys().collect::<Vec<_>>(),
+            vec!["a.txt", "sub/b.txt"]
+        );
+
+        let target_tree = scan(&target, "/mirror").await.unwrap();
+        let plan = SyncPlan::build(&source_tree, &target_tree, true);
+        let report = execute(&source, "/data", &target, "/mirror", &plan, 2, |_| {}).await;
+
+        assert_eq!(
+            report.summary(),
+            SyncSummary {
+                created: 2,
+                updated: 0,
+                deleted: 1,
+                failed: 0,
+                bytes_transferred: 4,
+            }
+        );
+        assert!(target_op.exists("mirror/sub/b.txt").await.unwrap());
+        assert!(!target_op.exists("mirror/stale.txt").await.unwrap());
+
+        // 再次同步时没有需要执行的动作
+        let plan = SyncPlan::build(
+            &scan(&source, "/data").await.unwrap(),
+            &scan(&target, "/mirror").await.unwrap(),
+            true,
+        );
+        assert!(plan.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_execute_reports_failures() {
+        let (source, _source_op, _source_dir) = create_file_manager();
+        let (target, _target_op, _target_dir) = create_file_manager();
+
+        // 源文件在计划生成后被删除
+        let plan = SyncPlan {
+            items: vec![SyncItem {
+                action: SyncAction::Create,
+                path: "gone.txt".to_string(),
+                size: 10,
+            }],
+        };
+        let report = execute(&source, "/", &target, "/", &plan, 4, |_| {}).await;
+
+        let summary = report.summary();
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.bytes_transferred, 0);
+    }
+
+    #[tokio::test]
+    async fn test_scan_missing_root_is_empty() {
+        let (file_manager, _operator, _temp_dir) = create_file_manager();
+
+        assert!(scan(&file_manager, "/missing").await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 基于令牌桶的带宽限制器
+///
+/// 同一个限制器可以在多个并发传输之间共享，所有传输合计不超过设定速率。
+/// 桶容量为一秒的流量，允许短时间突发。
+#[derive(Debug)]
+pub struct RateLimiter {
+    bytes_per_second: u64,
+    state: Mutex<BucketState>,
+}
+
+#[derive(Debug)]
+struct BucketState {
+    available: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// 创建限制器，速率单位为字节/秒
+    pub fn new(bytes_per_second: u64) -> Self {
+        let bytes_per_second = bytes_per_second.max(1);
+        Self {
+            bytes_per_second,
+            state: Mutex::new(BucketState {
+                available: bytes_per_second as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn bytes_per_second(&self) -> u64 {
+        self.bytes_per_second
+    }
+
+    /// 申请发送 `bytes` 字节的额度，额度不足时等待到可以发送为止
+    ///
+    /// 额度允许透支：大数据块先记账，再按欠下的额度等待，保证平均速率不超过限制。
+    pub async fn acquire(&self, bytes: u64) {
+        let wait = {
+            let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+            let rate = self.bytes_per_second as f64;
+            let now = Instant::now();
+            let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+            state.available = (state.available + elapsed * rate).min(rate);
+            state.last_refill = now;
+            state.available -= bytes as f64;
+
+            if state.available < 0.0 {
+                Some(Duration::from_secs_f64(-state.available / rate))
+            } else {
+                None
+            }
+        };
+
+        if let Some(wait) = wait {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// 限速时单次申请额度的最小字节数，避免切分得过碎
+const MIN_SLICE_SIZE: usize = 4 * 1024;
+
+/// 一次传输需要遵守的全部带宽限制（例如全局限制和连接自身的限制）
+#[derive(Debug, Clone, Default)]
+pub struct Throttle {
+    limiters: Vec<Arc<RateLimiter>>,
+}
+
+impl Throttle {
+    /// 添加一个限制器，同一个限制器只会生效一次
+    pub fn with_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        if !self.limiters.iter().any(|l| Arc::ptr_eq(l, &limiter)) {
+            self.limiters.push(limiter);
+        }
+        self
+    }
+
+    /// 合并两组限制，用于同时受源和目标连接约束的传输
+    pub fn merge(&self, other: &Throttle) -> Throttle {
+        other
+            .limiters
+            .iter()
+            .cloned()
+            .fold(self.clone(), |throttle, limiter| {
+                throttle.with_limiter(limiter)
+            })
+    }
+
+    pub fn is_unlimited(&self) -> bool {
+        self.limiters.is_empty()
+    }
+
+    /// 每次申请额度的最大字节数
+    ///
+    /// 不限速时为 `chunk_size`；限速时约为最低速率的十分之一，
+    /// 传输按这个大小切片申请额度，避免整块申请后长时间等待再突发发送。
+    pub fn slice_size(&self, chunk_size: usize) -> usize {
+        self.limiters
+            .iter()
+            .map(|l| (l.bytes_per_second() / 10).max(MIN_SLICE_SIZE as u64))
+            .min()
+            .map_or(chunk_size, |slice| chunk_size.min(slice as usize))
+            .max(1)
+    }
+
+    /// 按所有限制器的要求等待，之后才能发送 `bytes` 字节
+    pub async fn consume(&self, bytes: u64) {
+        for limiter in &self.limiters {
+            limiter.acquire(bytes).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rate_limiter_allows_initial_burst() {
+        let limiter = RateLimiter::new(100_000);
+        let start = Instant::now();
+        limiter.acquire(100_000).await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_waits_when_exhausted() {
+        let limiter = RateLimiter::new(100_000);
+        limiter.acquire(100_000).await;
+
+        let start = Instant::now();
+        limiter.acquire(20_000).await;
+        assert!(start.elapsed() >= Duration::from_millis(180));
+    }
+
+    #[test]
+    fn test_throttle_slice_size() {
+        let chunk_size = 8 * 1024 * 1024;
+        assert_eq!(Throttle::default().slice_size(chunk_size), chunk_size);
+
+        let throttle = Throttle::default()
+            .with_limiter(Arc::new(RateLimiter::new(1024 * 1024)))
+            .with_limiter(Arc::new(RateLimiter::new(500_000)));
+        assert_eq!(throttle.slice_size(chunk_size), 50_000);
+        assert_eq!(throttle.slice_size(1024), 1024);
+
+        let slow = Throttle::default().with_limiter(Arc::new(RateLimiter::new(100)));
+        assert_eq!(slow.slice_size(chunk_size), MIN_SLICE_SIZE);
+    }
+
+    #[tokio::test]
+    async fn test_throttle_shares_limiter_between_transfers() {
+        let limiter = Arc::new(RateLimiter::new(100_000));
+        let first = Throttle::default().with_limiter(limiter.clone());
+        let second = Throttle::default().with_limiter(limiter.clone());
+
+        // 同一个限制器合并后只计算一次
+        let merged = first.merge(&second);
+        assert_eq!(merged.limiters.len(), 1);
+
+        let start = Instant::now();
+        first.consume(60_000).await;
+        second.consume(60_000).await;
+        assert!(start.elapsed() >= Duration::from_millis(180));
+        assert!(Throttle::default().is_unlimited());
+    }
+}
@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use log::debug;
+use opendal::Metadata;
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::Result;
+
+/// 断点续传记录文件的后缀
+const CHECKPOINT_SUFFIX: &str = ".mpfm-resume";
+
+/// 目录批量传输时默认并发传输的文件数
+pub const DEFAULT_TRANSFER_CONCURRENCY: usize = 4;
+
+/// 传输方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    Upload,
+    Download,
+    /// 连接之间直接传输
+    Copy,
+}
+
+/// 单次进度回调时的传输状态快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProgress {
+    pub direction: TransferDirection,
+    pub path: String,
+    pub transferred: u64,
+    pub total: u64,
+    pub bytes_per_sec: f64,
+    pub eta_secs: Option<u64>,
+}
+
+impl TransferProgress {
+    /// 完成百分比（0-100）
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.transferred as f64 / self.total as f64 * 100.0
+        }
+    }
+}
+
+/// 进度跟踪器，按数据块累计字节数并计算速率与剩余时间
+#[derive(Debug)]
+pub struct ProgressTracker {
+    direction: TransferDirection,
+    path: String,
+    total: u64,
+    transferred: u64,
+    resumed_from: u64,
+    started: Instant,
+}
+
+impl ProgressTracker {
+    /// 创建新的进度跟踪器
+    pub fn new(direction: TransferDirection, path: &str, total: u64) -> Self {
+        Self {
+            direction,
+            path: path.to_string(),
+            total,
+            transferred: 0,
+            resumed_from: 0,
+            started: Instant::now(),
+        }
+    }
+
+    /// 从已传输的偏移量继续计数（续传时使用），速率只统计本次传输的字节
+    pub fn resume_from(mut self, offset: u64) -> Self {
+        self.transferred = offset;
+        self.resumed_from = offset;
+        self
+    }
+
+    /// 记录新传输的字节数，返回当前进度快照
+    pub fn advance(&mut self, bytes: u64) -> TransferProgress {
+        self.transferred += bytes;
+        self.snapshot()
+    }
+
+    /// 获取当前进度快照
+    pub fn snapshot(&self) -> TransferProgress {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let bytes_per_sec = if elapsed > 0.0 {
+            (self.transferred - self.resumed_from) as f64 / elapsed
+        } else {
+            0.0
+        };
+
+        let remaining = self.total.saturating_sub(self.transferred);
+        let eta_secs = if remaining == 0 {
+            Some(0)
+        } else if bytes_per_sec > 0.0 {
+            Some((remaining as f64 / bytes_per_sec).ceil() as u64)
+        } else {
+            None
+        };
+
+        TransferProgress {
+            direction: self.direction,
+            path: self.path.clone(),
+            transferred: self.transferred,
+            total: self.total,
+            bytes_per_sec,
+            eta_secs,
+        }
+    }
+}
+
+/// 批量传输中单个文件的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferResult {
+    pub source: String,
+    pub target: String,
+    pub size: u64,
+    pub error: Option<String>,
+}
+
+impl TransferResult {
+    /// 是否传输成功
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// 批量传输报告，记录每个文件的成功或失败
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransferReport {
+    pub results: Vec<TransferResult>,
+}
+
+impl TransferReport {
+    /// 成功传输的文件
+    pub fn succeeded(&self) -> impl Iterator<Item = &TransferResult> {
+        self.results.iter().filter(|r| r.is_success())
+    }
+
+    /// 传输失败的文件
+    pub fn failed(&self) -> impl Iterator<Item = &TransferResult> {
+        self.results.iter().filter(|r| !r.is_success())
+    }
+
+    /// 成功传输的总字节数
+    pub fn transferred_bytes(&self) -> u64 {
+        self.succeeded().map(|r| r.size).sum()
+    }
+}
+
+/// 批量传输进度，每完成一个文件回调一次
+#[derive(Debug, Clone)]
+pub struct BatchProgress<'a> {
+    pub completed: usize,
+    pub total: usize,
+    pub result: &'a TransferResult,
+}
+
+/// 下载断点记录，保存开始下载时远程文件的标识，用于续传前校验文件未被修改
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadCheckpoint {
+    pub etag: Option<String>,
+    pub content_length: u64,
+    pub last_modified: Option<String>,
+}
+
+impl DownloadCheckpoint {
+    /// 根据远程文件元数据创建断点记录
+    pub fn from_metadata(meta: &Metadata) -> Self {
+        Self {
+            etag: meta.etag().map(|s| s.to_string()),
+            content_length: meta.content_length(),
+            last_modified: meta.last_modified().map(|dt| dt.to_rfc3339()),
+        }
+    }
+
+    /// 断点记录文件路径（与本地文件同目录）
+    pub fn path_for(local_path: &Path) -> PathBuf {
+        let mut name = local_path.as_os_str().to_os_string();
+        name.push(CHECKPOINT_SUFFIX);
+        PathBuf::from(name)
+    }
+
+    /// 读取本地文件对应的断点记录，不存在或无法解析时返回 None
+    pub fn load(local_path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(Self::path_for(local_path)).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(checkpoint) => Some(checkpoint),
+            Err(e) => {
+                debug!("解析断点记录失败: {}", e);
+                None
+            }
+        }
+    }
+
+    /// 保存断点记录
+    pub fn save(&self, local_path: &Path) -> Result<()> {
+        let content = serde_json::to_string(self)?;
+        std::fs::write(Self::path_for(local_path), content)?;
+        Ok(())
+    }
+
+    /// 删除断点记录
+    pub fn remove(local_path: &Path) -> Result<()> {
+        let path = Self::path_for(local_path);
+        if path.exists() {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// 判断远程文件自记录以来是否未发生变化：优先比较 ETag，否则比较大小与修改时间
+    pub fn matches(&self, current: &DownloadCheckpoint) -> bool {
+        match (&self.etag, &current.etag) {
+            (Some(recorded), Some(remote)) => {
+                recorded == remote && self.content_length == current.content_length
+            }
+            _ => {
+                self.content_length == current.content_length
+                    && self.last_modified == current.last_modified
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_tracker_accumulates() {
+        let mut tracker = ProgressTracker::new(TransferDirection::Upload, "a.txt", 100);
+
+        let progress = tracker.advance(40);
+        assert_eq!(progress.transferred, 40);
+        assert_eq!(progress.total, 100);
+        assert_eq!(progress.direction, TransferDirection::Upload);
+
+        let progress = tracker.advance(60);
+        assert_eq!(progress.transferred, 100);
+        assert_eq!(progress.eta_secs, Some(0));
+        assert_eq!(progress.percent(), 100.0);
+    }
+
+    #[test]
+    fn test_progress_tracker_resume_from_offset() {
+        let mut tracker =
+            ProgressTracker::new(TransferDirection::Download, "a.txt", 100).resume_from(70);
+
+        let progress = tracker.advance(30);
+        assert_eq!(progress.transferred, 100);
+        assert_eq!(progress.eta_secs, Some(0));
+    }
+
+    #[test]
+    fn test_checkpoint_matches_prefers_etag() {
+        let recorded = DownloadCheckpoint {
+            etag: Some("\"abc\"".to_string()),
+            content_length: 10,
+            last_modified: None,
+        };
+
+        // ETag 一致时忽略修改时间的差异
+        let mut current = recorded.clone();
+        current.last_modified = Some("2024-01-01T00:00:00+00:00".to_string());
+        assert!(recorded.matches(&current));
+
+        current.etag = Some("\"def\"".to_string());
+        assert!(!recorded.matches(&current));
+    }
+
+    #[test]
+    fn test_checkpoint_matches_falls_back_to_size() {
+        let recorded = DownloadCheckpoint {
+            etag: None,
+            content_length: 10,
+            last_modified: Some("2024-01-01T00:00:00+00:00".to_string()),
+        };
+
+        assert!(recorded.matches(&recorded.clone()));
+
+        let mut current = recorded.clone();
+        current.content_length = 11;
+        assert!(!recorded.matches(&current));
+    }
+
+    #[test]
+    fn test_transfer_report_counts() {
+        let report = TransferReport {
+            results: vec![
+                TransferResult {
+                    source: "a".to_string(),
+                    target: "/a".to_string(),
+                    size: 10,
+                    error: None,
+                },
+                TransferResult {
+                    source: "b".to_string(),
+                    target: "/b".to_string(),
+                    size: 20,
+                    error: Some("denied".to_string()),
+                },
+            ],
+        };
+
+        assert_eq!(report.succeeded().count(), 1);
+        assert_eq!(report.failed().count(), 1);
+        assert_eq!(report.transferred_bytes(), 10);
+    }
+
+    #[test]
+    fn test_progress_percent_empty_file() {
+        let tracker = ProgressTracker::new(TransferDirection::Download, "empty", 0);
+        assert_eq!(tracker.snapshot().percent(), 100.0);
+    }
+}
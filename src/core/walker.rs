@@ -0,0 +1,373 @@
+use std::collections::{HashSet, VecDeque};
+
+use futures::stream::{self, BoxStream, StreamExt};
+use log::{debug, warn};
+use opendal::{Metadata, Operator};
+
+use crate::core::error::{Error, Result};
+
+/// 目录遍历选项
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    /// 最大遍历深度，根目录的直接子项深度为 1；None 表示不限制
+    pub max_depth: Option<usize>,
+    /// 包含规则（支持 `*`、`?` 通配符），为空时包含所有文件；只作用于文件
+    pub include: Vec<String>,
+    /// 排除规则（支持 `*`、`?` 通配符），被排除的目录不会继续遍历
+    pub exclude: Vec<String>,
+    /// 最多返回的条目数，达到后产生 `WalkEvent::Truncated`；None 表示不限制
+    pub limit: Option<usize>,
+}
+
+impl WalkOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    pub fn with_include(mut self, pattern: &str) -> Self {
+        self.include.push(pattern.to_string());
+        self
+    }
+
+    pub fn with_exclude(mut self, pattern: &str) -> Self {
+        self.exclude.push(pattern.to_string());
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// 规则不含 '/' 时匹配文件名，否则匹配相对路径
+    fn matches_any(patterns: &[String], name: &str, relative: &str) -> bool {
+        patterns.iter().any(|pattern| {
+            let target = if pattern.contains('/') {
+                relative
+            } else {
+                name
+            };
+            glob_match(pattern, target.trim_end_matches('/'))
+        })
+    }
+}
+
+/// 遍历过程中发现的条目
+#[derive(Debug, Clone)]
+pub struct WalkEntry {
+    /// 完整路径（以 '/' 开头，目录以 '/' 结尾）
+    pub path: String,
+    /// 相对于遍历根目录的路径
+    pub relative: String,
+    /// 深度，根目录的直接子项为 1
+    pub depth: usize,
+    pub metadata: Metadata,
+}
+
+impl WalkEntry {
+    pub fn is_dir(&self) -> bool {
+        self.metadata.is_dir()
+    }
+}
+
+/// 遍历流产生的事件
+#[derive(Debug)]
+pub enum WalkEvent {
+    Entry(Box<WalkEntry>),
+    /// 已达到调用方设置的条目上限，仍有未返回的条目
+    Truncated {
+        limit: usize,
+    },
+    /// 子目录列出失败，该目录下的内容被跳过，遍历继续
+    Failed {
+        /// 列出失败的目录（以 '/' 开头和结尾）
+        path: String,
+        error: Error,
+    },
+}
+
+impl WalkEvent {
+    /// 取出条目，截断事件返回 `None`；子目录列出失败时返回错误
+    ///
+    /// 用于复制、传输、同步等不能遗漏子目录的场景。
+    pub fn into_entry(self) -> Result<Option<Box<WalkEntry>>> {
+        match self {
+            WalkEvent::Entry(entry) => Ok(Some(entry)),
+            WalkEvent::Truncated { .. } => Ok(None),
+            WalkEvent::Failed { error, .. } => Err(error),
+        }
+    }
+}
+
+struct WalkState {
+    operator: Operator,
+    options: WalkOptions,
+    root: String,
+    pending_dirs: VecDeque<(String, usize)>,
+    visited: HashSet<String>,
+    buffer: VecDeque<WalkEntry>,
+    emitted: usize,
+    finished: bool,
+}
+
+/// 以流的方式遍历目录树，逐个目录列出内容，不会一次性收集整棵树
+///
+/// `root` 为相对于 Operator 根的目录路径（不以 '/' 开头），空字符串表示根目录。
+/// 根目录列出失败时返回错误并结束遍历；子目录列出失败时产生 `WalkEvent::Failed` 并继续遍历其余目录。
+pub fn walk(
+    operator: Operator,
+    root: &str,
+    options: WalkOptions,
+) -> BoxStream<'static, Result<WalkEvent>> {
+    let root = if root.is_empty() || root.ends_with('/') {
+        root.to_string()
+    } else {
+        format!("{}/", root)
+    };
+
+    let state = WalkState {
+        operator,
+        options,
+        pending_dirs: VecDeque::from([(root.clone(), 0)]),
+        root,
+        visited: HashSet::new(),
+        buffer: VecDeque::new(),
+        emitted: 0,
+        finished: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        if state.finished {
+            return None;
+        }
+
+        loop {
+            if let Some(entry) = state.buffer.pop_front() {
+                if let Some(limit) = state.options.limit {
+                    if state.emitted >= limit {
+                        debug!("遍历达到条目上限 {}", limit);
+                        state.finished = true;
+                        return Some((Ok(WalkEvent::Truncated { limit }), state));
+                    }
+                }
+                state.emitted += 1;
+                return Some((Ok(WalkEvent::Entry(Box::new(entry))), state));
+            }
+
+            let (dir, depth) = state.pending_dirs.pop_front()?;
+            if !state.visited.insert(dir.clone()) {
+                debug!("跳过已访问的目录: {}", dir);
+                continue;
+            }
+
+            debug!("遍历目录: {} (深度 {})", dir, depth);
+            let entries = match state.operator.list(&dir).await {
+                Ok(entries) => entries,
+                Err(e) if depth > 0 => {
+                    warn!("列出目录失败，跳过: {}: {}", dir, e);
+                    let event = WalkEvent::Failed {
+                        path: format!("/{}", dir),
+                        error: e.into(),
+                    };
+                    return Some((Ok(event), state));
+                }
+                Err(e) => {
+                    state.finished = true;
+                    return Some((Err(e.into()), state));
+                }
+            };
+
+            for entry in entries {
+                let path = entry.path();
+                // 列表结果可能包含目录自身（根目录表示为 "/"）
+                if path.trim_end_matches('/') == dir.trim_end_matches('/')
+                    || entry.name() == "."
+                    || entry.name() == ".."
+                {
+                    continue;
+                }
+
+                let relative = path.strip_prefix(&state.root).unwrap_or(path);
+                let name = entry.name().trim_end_matches('/');
+                if WalkOptions::matches_any(&state.options.exclude, name, relative) {
+                    continue;
+                }
+
+                let child_depth = depth + 1;
+                let metadata = entry.metadata().clone();
+                if metadata.is_dir() {
+                    if state.options.max_depth.is_none_or(|max| child_depth < max) {
+                        state
+                            .pending_dirs
+                            .push_back((path.to_string(), child_depth));
+                    }
+                } else if !state.options.include.is_empty()
+                    && !WalkOptions::matches_any(&state.options.include, name, relative)
+                {
+                    continue;
+                }
+
+                state.buffer.push_back(WalkEntry {
+                    path: format!("/{}", path),
+                    relative: relative.to_string(),
+                    depth: child_depth,
+                    metadata,
+                });
+            }
+        }
+    })
+    .boxed()
+}
+
+/// 简单的通配符匹配：`*` 匹配任意长度字符，`?` 匹配单个字符
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+    use opendal::services::Fs;
+    use tempfile::TempDir;
+
+    async fn create_tree() -> (Operator, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let builder = Fs::default().root(temp_dir.path().to_str().unwrap());
+        let operator = Operator::new(builder).unwrap().finish();
+
+        for path in ["a.txt", "b.log", "dir/c.txt", "dir/sub/d.txt", "skip/e.txt"] {
+            operator.write(path, "x").await.unwrap();
+        }
+        (operator, temp_dir)
+    }
+
+    async fn collect(operator: Operator, root: &str, options: WalkOptions) -> Vec<WalkEvent> {
+        walk(operator, root, options).try_collect().await.unwrap()
+    }
+
+    fn file_paths(events: &[WalkEvent]) -> Vec<String> {
+        let mut paths: Vec<String> = events
+            .iter()
+            .filter_map(|event| match event {
+                WalkEvent::Entry(entry) if !entry.is_dir() => Some(entry.path.clone()),
+                _ => None,
+            })
+            .collect();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.txt", "a.txt"));
+        assert!(glob_match("dir/*", "dir/c.txt"));
+        assert!(glob_match("?.log", "b.log"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("*.txt", "b.log"));
+        assert!(!glob_match("a?", "a"));
+    }
+
+    #[tokio::test]
+    async fn test_walk_all_files() {
+        let (operator, _temp_dir) = create_tree().await;
+
+        let events = collect(operator, "", WalkOptions::new()).await;
+
+        assert_eq!(
+            file_paths(&events),
+            vec![
+                "/a.txt",
+                "/b.log",
+                "/dir/c.txt",
+                "/dir/sub/d.txt",
+                "/skip/e.txt"
+            ]
+        );
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, WalkEvent::Truncated { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_walk_depth_and_filters() {
+        let (operator, _temp_dir) = create_tree().await;
+
+        let options = WalkOptions::new()
+            .with_max_depth(2)
+            .with_include("*.txt")
+            .with_exclude("skip");
+        let events = collect(operator, "", options).await;
+
+        assert_eq!(file_paths(&events), vec!["/a.txt", "/dir/c.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_walk_continues_after_subdirectory_error() {
+        let (operator, temp_dir) = create_tree().await;
+
+        // 遍历开始后把 skip 目录替换为同名文件，之后列出该目录会失败
+        let mut stream = walk(operator, "", WalkOptions::new());
+        let mut events = vec![stream.try_next().await.unwrap().unwrap()];
+        std::fs::remove_dir_all(temp_dir.path().join("skip")).unwrap();
+        std::fs::write(temp_dir.path().join("skip"), "x").unwrap();
+        while let Some(event) = stream.try_next().await.unwrap() {
+            events.push(event);
+        }
+
+        let failed: Vec<&str> = events
+            .iter()
+            .filter_map(|event| match event {
+                WalkEvent::Failed { path, .. } => Some(path.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(failed, vec!["/skip/"]);
+        assert_eq!(
+            file_paths(&events),
+            vec!["/a.txt", "/b.log", "/dir/c.txt", "/dir/sub/d.txt"]
+        );
+
+        let err = events
+            .into_iter()
+            .map(WalkEvent::into_entry)
+            .find_map(|entry| entry.err());
+        assert!(err.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_walk_limit_signals_truncation() {
+        let (operator, _temp_dir) = create_tree().await;
+
+        let events = collect(operator, "dir", WalkOptions::new().with_limit(2)).await;
+
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[2], WalkEvent::Truncated { limit: 2 }));
+    }
+}
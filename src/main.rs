@@ -8,14 +8,19 @@ mod protocols;
 mod utils;
 
 // Import commands directly
+use commands::bucket;
 use commands::config;
 use commands::connection;
 use commands::file;
+use commands::state::AppState;
 
 fn main() {
     env_logger::init();
 
+    let state = AppState::load();
+
     tauri::Builder::default()
+        .manage(state)
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -26,14 +31,22 @@ fn main() {
             connection::remove_connection,
             connection::copy_connection,
             connection::update_connection,
-            connection::check_s3_bucket_exists,
-            connection::create_s3_bucket,
+            connection::detect_s3_addressing_style,
+            bucket::check_s3_bucket_exists,
+            bucket::create_s3_bucket,
+            bucket::list_s3_buckets,
+            bucket::discover_s3_buckets,
+            bucket::head_s3_bucket,
+            bucket::delete_s3_bucket,
             file::list_files,
             file::list_files_paginated,
             file::upload_file,
             file::download_file,
             file::batch_download_files,
             file::delete_file,
+            file::copy_file,
+            file::rename_file,
+            file::transfer_between_connections,
             file::create_directory,
             file::get_directory_count,
             file::search_files,
@@ -31,12 +31,12 @@ fn main() {
             Ok(mut app) => {
                 if let Err(e) = app.run().await {
                     error!("运行错误: {}", e);
-                    process::exit(1);
+                    process::exit(e.kind().exit_code());
                 }
             }
             Err(e) => {
                 error!("初始化应用程序失败: {}", e);
-                process::exit(1);
+                process::exit(e.kind().exit_code());
             }
         }
     });
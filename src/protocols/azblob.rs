@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+
+use log::debug;
+use opendal::{services, Operator};
+
+use super::limits::{apply_layers, ConnectionLimits};
+use super::retry::RetryPolicy;
+use super::traits::{Capabilities, Protocol};
+use crate::core::error::{Error, Result};
+
+/// 连接配置中认证相关的键
+pub const ACCOUNT_NAME_KEY: &str = "account_name";
+pub const ACCOUNT_KEY_KEY: &str = "account_key";
+pub const SAS_TOKEN_KEY: &str = "sas_token";
+pub const CONNECTION_STRING_KEY: &str = "connection_string";
+
+/// Azurite 模拟器的固定账号，见 `UseDevelopmentStorage=true`
+const AZURITE_ACCOUNT_NAME: &str = "devstoreaccount1";
+const AZURITE_ACCOUNT_KEY: &str =
+    "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+const AZURITE_BLOB_ENDPOINT: &str = "http://127.0.0.1:10000/devstoreaccount1";
+
+/// Azure Blob 的认证方式
+///
+/// 连接字符串在解析时展开为账号密钥或 SAS，不单独作为一种认证方式保存。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AzblobAuth {
+    /// 存储账号共享密钥
+    AccountKey {
+        account_name: String,
+        account_key: String,
+    },
+    /// 共享访问签名（不带前导 '?'）
+    Sas {
+        account_name: Option<String>,
+        token: String,
+    },
+}
+
+impl AzblobAuth {
+    pub fn account_name(&self) -> Option<&str> {
+        match self {
+            Self::AccountKey { account_name, .. } => Some(account_name),
+            Self::Sas { account_name, .. } => account_name.as_deref(),
+        }
+    }
+
+    fn sas(account_name: Option<String>, token: &str) -> Self {
+        Self::Sas {
+            account_name,
+            token: token.trim_start_matches('?').to_string(),
+        }
+    }
+}
+
+/// 解析 Azure Storage 连接字符串，返回认证方式和 Blob 端点
+///
+/// 支持 `UseDevelopmentStorage=true`（Azurite）、`BlobEndpoint` 以及
+/// `DefaultEndpointsProtocol` + `EndpointSuffix` 的写法。
+pub fn parse_connection_string(conn: &str) -> Result<(AzblobAuth, Option<String>)> {
+    let values: HashMap<&str, &str> = conn
+        .split(';')
+        .filter_map(|part| part.trim().split_once('='))
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .collect();
+
+    if values
+        .get("UseDevelopmentStorage")
+        .is_some_and(|v| v.eq_ignore_ascii_case("true"))
+    {
+        return Ok((
+            AzblobAuth::AccountKey {
+                account_name: AZURITE_ACCOUNT_NAME.to_string(),
+                account_key: AZURITE_ACCOUNT_KEY.to_string(),
+            },
+            Some(AZURITE_BLOB_ENDPOINT.to_string()),
+        ));
+    }
+
+    let account_name = values.get("AccountName").map(|v| v.to_string());
+    let auth = match (
+        values.get("AccountKey"),
+        values.get("SharedAccessSignature"),
+    ) {
+        (_, Some(token)) => AzblobAuth::sas(account_name.clone(), token),
+        (Some(key), None) => AzblobAuth::AccountKey {
+            account_name: account_name
+                .clone()
+                .ok_or_else(|| Error::new_config("Azure 连接字符串缺少 'AccountName'"))?,
+            account_key: key.to_string(),
+        },
+        (None, None) => {
+            return Err(Error::new_config(
+                "Azure 连接字符串缺少 'AccountKey' 或 'SharedAccessSignature'",
+            ))
+        }
+    };
+
+    let endpoint = match (values.get("BlobEndpoint"), values.get("EndpointSuffix")) {
+        (Some(endpoint), _) => Some(endpoint.to_string()),
+        (None, Some(suffix)) => {
+            let account_name = account_name.ok_or_else(|| {
+                Error::new_config("Azure 连接字符串使用 'EndpointSuffix' 时需要 'AccountName'")
+            })?;
+            let scheme = values
+                .get("DefaultEndpointsProtocol")
+                .copied()
+                .unwrap_or("https");
+            Some(format!("{}://{}.blob.{}", scheme, account_name, suffix))
+        }
+        (None, None) => None,
+    };
+
+    Ok((auth, endpoint))
+}
+
+#[derive(Debug)]
+pub struct AzblobProtocol {
+    container: String,
+    /// Blob 服务端点，例如 `https://account.blob.core.windows.net`
+    endpoint: String,
+    auth: AzblobAuth,
+    root: Option<String>,
+    retry: RetryPolicy,
+    limits: ConnectionLimits,
+}
+
+impl AzblobProtocol {
+    /// 创建 Azure Blob 协议实例，未指定端点时使用公有云默认端点
+    pub fn new(
+        container: String,
+        auth: AzblobAuth,
+        endpoint: Option<String>,
+        root: Option<String>,
+    ) -> Result<Self> {
+        let endpoint = match endpoint.filter(|e| !e.is_empty()) {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => match auth.account_name() {
+                Some(account_name) => format!("https://{}.blob.core.windows.net", account_name),
+                None => {
+                    return Err(Error::new_config(
+                        "Azure Blob配置缺少 'endpoint' 或 'account_name' 参数",
+                    ))
+                }
+            },
+        };
+
+        Ok(Self {
+            container,
+            endpoint,
+            auth,
+            root,
+            retry: RetryPolicy::default(),
+            limits: ConnectionLimits::default(),
+        })
+    }
+
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置超时与并发限制
+    pub fn with_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let non_empty = |key: &str| config.get(key).filter(|v| !v.is_empty()).cloned();
+
+        let container = non_empty("container")
+            .ok_or_else(|| Error::new_config("Azure Blob配置缺少 'container' 参数"))?;
+
+        let (auth, conn_endpoint) = match non_empty(CONNECTION_STRING_KEY) {
+            Some(conn) => {
+                if non_empty(ACCOUNT_KEY_KEY).is_some() || non_empty(SAS_TOKEN_KEY).is_some() {
+                    return Err(Error::new_config(
+                        "Azure Blob配置不能同时指定 'connection_string' 和 'account_key'/'sas_token'",
+                    ));
+                }
+                parse_connection_string(&conn)?
+            }
+            None => {
+                let account_name = non_empty(ACCOUNT_NAME_KEY);
+                let auth = match (non_empty(ACCOUNT_KEY_KEY), non_empty(SAS_TOKEN_KEY)) {
+                    (Some(_), Some(_)) => {
+                        return Err(Error::new_config(
+                            "Azure Blob配置不能同时指定 'account_key' 和 'sas_token'",
+                        ))
+                    }
+                    (Some(account_key), None) => AzblobAuth::AccountKey {
+                        account_name: account_name.ok_or_else(|| {
+                            Error::new_config("Azure Blob配置缺少 'account_name' 参数")
+                        })?,
+                        account_key,
+                    },
+                    (None, Some(token)) => AzblobAuth::sas(account_name, &token),
+                    (None, None) => return Err(Error::new_config(
+                        "Azure Blob配置缺少 'account_key'、'sas_token' 或 'connection_string' 参数",
+                    )),
+                };
+                (auth, None)
+            }
+        };
+
+        // 显式配置的端点优先于连接字符串中的端点
+        let endpoint = non_empty("endpoint").or(conn_endpoint);
+        let root = config
+            .get("root_dir")
+            .or_else(|| config.get("root"))
+            .filter(|r| !r.is_empty())
+            .cloned();
+
+        Ok(Self::new(container, auth, endpoint, root)?
+            .with_retry(RetryPolicy::from_config(config)?)
+            .with_limits(ConnectionLimits::from_config(config)?))
+    }
+}
+
+impl Protocol for AzblobProtocol {
+    fn create_operator(&self) -> Result<Operator> {
+        debug!(
+            "创建 Azure Blob 操作符, container: {}, endpoint: {}",
+            self.container, self.endpoint
+        );
+
+        let mut builder = services::Azblob::default()
+            .container(&self.container)
+            .endpoint(&self.endpoint);
+
+        match &self.auth {
+            AzblobAuth::AccountKey {
+                account_name,
+                account_key,
+            } => {
+                builder = builder.account_name(account_name).account_key(account_key);
+            }
+            AzblobAuth::Sas {
+                account_name,
+                token,
+            } => {
+                debug!("使用 SAS 认证");
+                if let Some(account_name) = account_name {
+                    builder = builder.account_name(account_name);
+                }
+                builder = builder.sas_token(token);
+            }
+        }
+
+        if let Some(root) = &self.root {
+            debug!("使用根目录: {}", root);
+            builder = builder.root(root);
+        }
+
+        let op = Operator::new(builder)?.finish();
+
+        Ok(apply_layers(op, &self.retry, &self.limits, "azblob"))
+    }
+
+    fn get_id(&self) -> String {
+        format!("azblob:{}", self.container)
+    }
+
+    fn get_name(&self) -> String {
+        format!("Azure Blob ({}) @ {}", self.container, self.endpoint)
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        // 目录由 OpenDAL 以空的 "dir/" blob 模拟；Blob 服务没有重命名，
+        // 移动时由 FileManager 改为复制后删除
+        Capabilities::default()
+            .with_list(true)
+            .with_read(true)
+            .with_write(true)
+            .with_delete(true)
+            .with_create_dir(true)
+            .with_copy(true)
+            .with_rename(false)
+            .with_batch_delete(true)
+    }
+
+    fn get_rate_limit(&self) -> Option<u64> {
+        self.limits.rate_limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("container".to_string(), "data".to_string());
+        config
+    }
+
+    #[test]
+    fn test_azblob_protocol_from_config_account_key() {
+        let mut config = base_config();
+        config.insert(ACCOUNT_NAME_KEY.to_string(), "myaccount".to_string());
+        config.insert(ACCOUNT_KEY_KEY.to_string(), "a2V5".to_string());
+        config.insert("root".to_string(), "/backups".to_string());
+
+        let protocol = AzblobProtocol::from_config(&config).unwrap();
+        assert_eq!(protocol.container, "data");
+        assert_eq!(protocol.endpoint, "https://myaccount.blob.core.windows.net");
+        assert_eq!(
+            protocol.auth,
+            AzblobAuth::AccountKey {
+                account_name: "myaccount".to_string(),
+                account_key: "a2V5".to_string()
+            }
+        );
+        assert_eq!(protocol.root, Some("/backups".to_string()));
+        assert!(protocol.create_operator().is_ok());
+    }
+
+    #[test]
+    fn test_azblob_protocol_from_config_sas() {
+        let mut config = base_config();
+        config.insert(
+            SAS_TOKEN_KEY.to_string(),
+            "?sv=2022-11-02&sig=abc".to_string(),
+        );
+
+        // 没有账号名时必须提供端点
+        assert!(AzblobProtocol::from_config(&config).is_err());
+
+        config.insert(
+            "endpoint".to_string(),
+            "https://myaccount.blob.core.windows.net/".to_string(),
+        );
+        let protocol = AzblobProtocol::from_config(&config).unwrap();
+        assert_eq!(protocol.endpoint, "https://myaccount.blob.core.windows.net");
+        assert_eq!(
+            protocol.auth,
+            AzblobAuth::Sas {
+                account_name: None,
+                token: "sv=2022-11-02&sig=abc".to_string()
+            }
+        );
+
+        config.insert(ACCOUNT_KEY_KEY.to_string(), "a2V5".to_string());
+        assert!(AzblobProtocol::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_parse_connection_string() {
+        let (auth, endpoint) = parse_connection_string("UseDevelopmentStorage=true").unwrap();
+        assert_eq!(auth.account_name(), Some(AZURITE_ACCOUNT_NAME));
+        assert_eq!(endpoint.as_deref(), Some(AZURITE_BLOB_ENDPOINT));
+
+        let (auth, endpoint) = parse_connection_string(
+            "DefaultEndpointsProtocol=https;AccountName=myaccount;AccountKey=a2V5==;EndpointSuffix=core.chinacloudapi.cn",
+        )
+        .unwrap();
+        assert_eq!(
+            auth,
+            AzblobAuth::AccountKey {
+                account_name: "myaccount".to_string(),
+                account_key: "a2V5==".to_string()
+            }
+        );
+        assert_eq!(
+            endpoint.as_deref(),
+            Some("https://myaccount.blob.core.chinacloudapi.cn")
+        );
+
+        let (auth, endpoint) = parse_connection_string(
+            "BlobEndpoint=https://myaccount.blob.core.windows.net/;SharedAccessSignature=sv=2022-11-02&sig=abc",
+        )
+        .unwrap();
+        assert_eq!(auth, AzblobAuth::sas(None, "sv=2022-11-02&sig=abc"));
+        assert_eq!(
+            endpoint.as_deref(),
+            Some("https://myaccount.blob.core.windows.net/")
+        );
+
+        assert!(parse_connection_string("AccountName=myaccount").is_err());
+        assert!(parse_connection_string("AccountKey=a2V5").is_err());
+    }
+
+    #[test]
+    fn test_azblob_protocol_from_config_connection_string() {
+        let mut config = base_config();
+        config.insert(
+            CONNECTION_STRING_KEY.to_string(),
+            "UseDevelopmentStorage=true".to_string(),
+        );
+
+        let protocol = AzblobProtocol::from_config(&config).unwrap();
+        assert_eq!(protocol.endpoint, AZURITE_BLOB_ENDPOINT);
+        assert_eq!(protocol.get_id(), "azblob:data");
+        assert_eq!(
+            protocol.get_name(),
+            "Azure Blob (data) @ http://127.0.0.1:10000/devstoreaccount1"
+        );
+
+        // 显式端点覆盖连接字符串中的端点
+        config.insert(
+            "endpoint".to_string(),
+            "http://azurite:10000/devstoreaccount1".to_string(),
+        );
+        let protocol = AzblobProtocol::from_config(&config).unwrap();
+        assert_eq!(protocol.endpoint, "http://azurite:10000/devstoreaccount1");
+
+        config.insert(SAS_TOKEN_KEY.to_string(), "sig=abc".to_string());
+        assert!(AzblobProtocol::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_azblob_protocol_missing_config() {
+        assert!(AzblobProtocol::from_config(&HashMap::new()).is_err());
+        assert!(AzblobProtocol::from_config(&base_config()).is_err());
+
+        // 账号密钥需要账号名
+        let mut config = base_config();
+        config.insert(ACCOUNT_KEY_KEY.to_string(), "a2V5".to_string());
+        assert!(AzblobProtocol::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_azblob_protocol_capabilities() {
+        let protocol = AzblobProtocol::new(
+            "data".to_string(),
+            AzblobAuth::sas(Some("myaccount".to_string()), "sig=abc"),
+            None,
+            None,
+        )
+        .unwrap();
+        let caps = protocol.get_capabilities();
+        assert!(caps.can_list);
+        assert!(caps.can_read);
+        assert!(caps.can_write);
+        assert!(caps.can_delete);
+        assert!(caps.can_create_dir);
+        assert!(caps.can_copy);
+        assert!(caps.can_batch_delete);
+        assert!(!caps.can_rename); // Blob 服务没有重命名操作
+    }
+}
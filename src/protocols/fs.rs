@@ -4,6 +4,8 @@ use std::path::{Path, PathBuf};
 use log::debug;
 use opendal::{services, Operator};
 
+use super::limits::{apply_layers, ConnectionLimits};
+use super::retry::RetryPolicy;
 use super::traits::{Capabilities, Protocol};
 use crate::core::error::{Error, Result};
 
@@ -11,6 +13,8 @@ use crate::core::error::{Error, Result};
 #[derive(Debug)]
 pub struct FSProtocol {
     root_dir: PathBuf,
+    retry: RetryPolicy,
+    limits: ConnectionLimits,
 }
 
 impl FSProtocol {
@@ -18,9 +22,23 @@ impl FSProtocol {
     pub fn new<P: AsRef<Path>>(root_dir: P) -> Self {
         Self {
             root_dir: root_dir.as_ref().to_path_buf(),
+            retry: RetryPolicy::default(),
+            limits: ConnectionLimits::default(),
         }
     }
 
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置超时与并发限制
+    pub fn with_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     /// 从配置创建适配器
     pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
         let root_dir = config
@@ -37,7 +55,9 @@ impl FSProtocol {
             return Err(Error::new_config(&format!("路径不是目录: {}", root_dir)));
         }
 
-        Ok(Self::new(path))
+        Ok(Self::new(path)
+            .with_retry(RetryPolicy::from_config(config)?)
+            .with_limits(ConnectionLimits::from_config(config)?))
     }
 }
 
@@ -54,7 +74,7 @@ impl Protocol for FSProtocol {
             Err(e) => return Err(Error::from(e)),
         };
 
-        Ok(op)
+        Ok(apply_layers(op, &self.retry, &self.limits, "fs"))
     }
 
     fn get_id(&self) -> String {
@@ -72,5 +92,11 @@ impl Protocol for FSProtocol {
             .with_write(true)
             .with_delete(true)
             .with_create_dir(true)
+            .with_copy(true)
+            .with_rename(true)
+    }
+
+    fn get_rate_limit(&self) -> Option<u64> {
+        self.limits.rate_limit
     }
 }
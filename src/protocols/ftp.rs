@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use log::debug;
 use opendal::{services, Operator};
 
+use super::limits::{apply_layers, ConnectionLimits};
+use super::retry::RetryPolicy;
 use super::traits::{Capabilities, Protocol};
 use crate::core::error::{Error, Result};
 
@@ -14,6 +16,8 @@ pub struct FtpProtocol {
     password: String,
     root: Option<String>,
     secure: bool,
+    retry: RetryPolicy,
+    limits: ConnectionLimits,
 }
 
 impl FtpProtocol {
@@ -32,9 +36,23 @@ impl FtpProtocol {
             password,
             root,
             secure,
+            retry: RetryPolicy::default(),
+            limits: ConnectionLimits::default(),
         }
     }
 
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置超时与并发限制
+    pub fn with_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
     pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
         let host = config
             .get("host")
@@ -65,7 +83,9 @@ impl FtpProtocol {
             .unwrap_or(Ok(false))
             .unwrap_or(false);
 
-        Ok(Self::new(host, port, username, password, root, secure))
+        Ok(Self::new(host, port, username, password, root, secure)
+            .with_retry(RetryPolicy::from_config(config)?)
+            .with_limits(ConnectionLimits::from_config(config)?))
     }
 }
 
@@ -94,7 +114,7 @@ impl Protocol for FtpProtocol {
             Err(e) => return Err(Error::from(e)),
         };
 
-        Ok(op)
+        Ok(apply_layers(op, &self.retry, &self.limits, "ftp"))
     }
 
     fn get_id(&self) -> String {
@@ -117,6 +137,10 @@ impl Protocol for FtpProtocol {
             .with_rename(true)
             .with_batch_delete(false)
     }
+
+    fn get_rate_limit(&self) -> Option<u64> {
+        self.limits.rate_limit
+    }
 }
 
 #[cfg(test)]
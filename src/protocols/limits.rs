@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use log::debug;
+use opendal::layers::{ConcurrentLimitLayer, TimeoutLayer};
+use opendal::Operator;
+
+use super::retry::{parse_value, RetryPolicy};
+use crate::core::error::{Error, Result};
+use crate::utils::format::parse_size;
+
+/// 连接配置中超时、并发与带宽限制的键
+pub const TIMEOUT_SECS_KEY: &str = "timeout_secs";
+pub const IO_TIMEOUT_SECS_KEY: &str = "io_timeout_secs";
+pub const MAX_CONCURRENT_REQUESTS_KEY: &str = "max_concurrent_requests";
+pub const LIMIT_RATE_KEY: &str = "limit_rate";
+
+/// 单个连接的超时、并发与带宽限制
+///
+/// 超时为 `None` 表示不限制；配置中写 0 即可关闭对应超时。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConnectionLimits {
+    /// 非 IO 操作（stat、list、delete、copy、rename 等）的超时时间
+    ///
+    /// 默认不限制：服务端复制或重命名大文件可能需要很长时间，需要时由用户显式开启。
+    pub timeout: Option<Duration>,
+    /// 单次读写数据的超时时间，传输过程中长时间没有进展即视为失败
+    pub io_timeout: Option<Duration>,
+    /// 同一操作符允许同时进行的请求数
+    pub max_concurrent_requests: Option<usize>,
+    /// 传输带宽上限（字节/秒），由文件管理器在读写数据时执行
+    pub rate_limit: Option<u64>,
+}
+
+impl Default for ConnectionLimits {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            io_timeout: Some(Duration::from_secs(30)),
+            max_concurrent_requests: None,
+            rate_limit: None,
+        }
+    }
+}
+
+impl ConnectionLimits {
+    /// 不限制超时、并发与带宽
+    pub fn unlimited() -> Self {
+        Self {
+            timeout: None,
+            io_timeout: None,
+            max_concurrent_requests: None,
+            rate_limit: None,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_io_timeout(mut self, io_timeout: Option<Duration>) -> Self {
+        self.io_timeout = io_timeout;
+        self
+    }
+
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: Option<usize>) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests;
+        self
+    }
+
+    pub fn with_rate_limit(mut self, rate_limit: Option<u64>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// 从连接配置读取限制，未配置的项使用默认值
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let default = Self::default();
+
+        let timeout = match parse_value::<u64>(config, TIMEOUT_SECS_KEY)? {
+            Some(secs) => secs_to_timeout(secs),
+            None => default.timeout,
+        };
+        let io_timeout = match parse_value::<u64>(config, IO_TIMEOUT_SECS_KEY)? {
+            Some(secs) => secs_to_timeout(secs),
+            None => default.io_timeout,
+        };
+        let max_concurrent_requests =
+            match parse_value::<usize>(config, MAX_CONCURRENT_REQUESTS_KEY)? {
+                Some(0) => {
+                    return Err(Error::new_config(&format!(
+                        "'{}' 至少为 1",
+                        MAX_CONCURRENT_REQUESTS_KEY
+                    )))
+                }
+                Some(n) => Some(n),
+                None => default.max_concurrent_requests,
+            };
+
+        // 速率支持 "512K"、"5M" 等写法，0 表示不限速
+        let rate_limit = match config.get(LIMIT_RATE_KEY).map(|v| v.trim()) {
+            None | Some("") => default.rate_limit,
+            Some(value) => match parse_size(value) {
+                Some(0) => None,
+                Some(rate) => Some(rate),
+                None => {
+                    return Err(Error::new_config(&format!(
+                        "配置项 '{}' 的值无效: {}",
+                        LIMIT_RATE_KEY, value
+                    )))
+                }
+            },
+        };
+
+        Ok(Self {
+            timeout,
+            io_timeout,
+            max_concurrent_requests,
+            rate_limit,
+        })
+    }
+}
+
+fn secs_to_timeout(secs: u64) -> Option<Duration> {
+    if secs == 0 {
+        None
+    } else {
+        Some(Duration::from_secs(secs))
+    }
+}
+
+/// 按固定顺序为操作符挂载超时、重试与并发限制层
+///
+/// 超时层必须位于重试层内侧：超时会直接丢弃进行中的 future，
+/// 放在外侧会让重试层处于不一致的状态。
+pub fn apply_layers(
+    operator: Operator,
+    retry: &RetryPolicy,
+    limits: &ConnectionLimits,
+    service: &str,
+) -> Operator {
+    let mut operator = operator;
+
+    if limits.timeout.is_some() || limits.io_timeout.is_some() {
+        debug!(
+            "{} 操作符超时设置: 操作 {:?}, IO {:?}",
+            service, limits.timeout, limits.io_timeout
+        );
+        // TimeoutLayer 没有"不限制"选项，未设置的一项用足够大的值代替
+        let unlimited = Duration::from_secs(u32::MAX as u64);
+        operator = operator.layer(
+            TimeoutLayer::new()
+                .with_timeout(limits.timeout.unwrap_or(unlimited))
+                .with_io_timeout(limits.io_timeout.unwrap_or(unlimited)),
+        );
+    }
+
+    operator = retry.apply(operator, service);
+
+    if let Some(permits) = limits.max_concurrent_requests {
+        debug!("{} 操作符最大并发请求数: {}", service, permits);
+        operator = operator.layer(ConcurrentLimitLayer::new(permits));
+    }
+
+    operator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opendal::services;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_config_uses_defaults() {
+        let limits = ConnectionLimits::from_config(&HashMap::new()).unwrap();
+        assert_eq!(limits, ConnectionLimits::default());
+        // 默认只限制单次读写，不限制可能耗时很长的服务端复制与重命名
+        assert_eq!(limits.timeout, None);
+        assert_eq!(limits.io_timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn test_from_config_overrides() {
+        let limits = ConnectionLimits::from_config(&config(&[
+            (TIMEOUT_SECS_KEY, "15"),
+            (IO_TIMEOUT_SECS_KEY, "0"),
+            (MAX_CONCURRENT_REQUESTS_KEY, "8"),
+            (LIMIT_RATE_KEY, "5M"),
+        ]))
+        .unwrap();
+
+        assert_eq!(limits.timeout, Some(Duration::from_secs(15)));
+        assert_eq!(limits.io_timeout, None);
+        assert_eq!(limits.max_concurrent_requests, Some(8));
+        assert_eq!(limits.rate_limit, Some(5 * 1024 * 1024));
+    }
+
+    #[test]
+    fn test_from_config_rejects_invalid_values() {
+        assert!(ConnectionLimits::from_config(&config(&[(TIMEOUT_SECS_KEY, "-1")])).is_err());
+        assert!(
+            ConnectionLimits::from_config(&config(&[(MAX_CONCURRENT_REQUESTS_KEY, "0")])).is_err()
+        );
+        assert!(ConnectionLimits::from_config(&config(&[(LIMIT_RATE_KEY, "fast")])).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_apply_layers_keeps_operator_usable() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let limits = ConnectionLimits::default().with_max_concurrent_requests(Some(1));
+        let op = apply_layers(op, &RetryPolicy::default(), &limits, "memory");
+
+        op.write("a.txt", "hello").await.unwrap();
+        assert_eq!(op.read("a.txt").await.unwrap().to_vec(), b"hello");
+    }
+}
@@ -1,8 +1,15 @@
+pub mod azblob;
 pub mod fs;
 pub mod s3;
 pub mod ftp;
+pub mod sftp;
+pub mod webdav;
+pub mod limits;
+pub mod retry;
 pub mod traits;
 
+pub use limits::ConnectionLimits;
+pub use retry::RetryPolicy;
 pub use traits::Protocol;
 
 /// 工厂函数根据协议类型创建协议实例
@@ -23,6 +30,18 @@ pub fn create_protocol(
             let protocol = ftp::FtpProtocol::from_config(config)?;
             Ok(Box::new(protocol))
         }
+        "sftp" => {
+            let protocol = sftp::SftpProtocol::from_config(config)?;
+            Ok(Box::new(protocol))
+        }
+        "webdav" => {
+            let protocol = webdav::WebdavProtocol::from_config(config)?;
+            Ok(Box::new(protocol))
+        }
+        "azblob" => {
+            let protocol = azblob::AzblobProtocol::from_config(config)?;
+            Ok(Box::new(protocol))
+        }
         // 其他协议类型在这里添加
         _ => Err(crate::core::error::Error::new_not_supported(&format!(
             "不支持的协议类型: {}",
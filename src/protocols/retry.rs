@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use log::{debug, warn};
+use opendal::layers::RetryLayer;
+use opendal::Operator;
+
+use crate::core::error::{Error, Result};
+
+/// 连接配置中覆盖重试策略的键
+pub const RETRY_MAX_ATTEMPTS_KEY: &str = "retry_max_attempts";
+pub const RETRY_MIN_BACKOFF_MS_KEY: &str = "retry_min_backoff_ms";
+pub const RETRY_MAX_BACKOFF_MS_KEY: &str = "retry_max_backoff_ms";
+pub const RETRY_BACKOFF_FACTOR_KEY: &str = "retry_backoff_factor";
+pub const RETRY_JITTER_KEY: &str = "retry_jitter";
+
+/// 操作失败时的重试策略（指数退避）
+///
+/// 只有 OpenDAL 判定为临时性的错误（限流、网络抖动、5xx 等）才会重试。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（包含首次请求），为 1 时不重试
+    pub max_attempts: usize,
+    /// 首次重试前的等待时间
+    pub min_backoff: Duration,
+    /// 单次等待时间上限
+    pub max_backoff: Duration,
+    /// 每次重试等待时间的增长倍数
+    pub factor: f32,
+    /// 是否在等待时间上叠加随机抖动，避免并发请求同时重试
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            min_backoff: Duration::from_millis(1000),
+            max_backoff: Duration::from_secs(60),
+            factor: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 不进行任何重试的策略
+    pub fn disabled() -> Self {
+        Self::default().with_max_attempts(1)
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_backoff(mut self, min_backoff: Duration, max_backoff: Duration) -> Self {
+        self.min_backoff = min_backoff;
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    pub fn with_factor(mut self, factor: f32) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// 从连接配置读取重试策略，未配置的项使用默认值
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let default = Self::default();
+
+        let max_attempts =
+            parse_value(config, RETRY_MAX_ATTEMPTS_KEY)?.unwrap_or(default.max_attempts);
+        let min_backoff = parse_value(config, RETRY_MIN_BACKOFF_MS_KEY)?
+            .map(Duration::from_millis)
+            .unwrap_or(default.min_backoff);
+        let max_backoff = parse_value(config, RETRY_MAX_BACKOFF_MS_KEY)?
+            .map(Duration::from_millis)
+            .unwrap_or(default.max_backoff);
+        let factor = parse_value(config, RETRY_BACKOFF_FACTOR_KEY)?.unwrap_or(default.factor);
+        let jitter = parse_value(config, RETRY_JITTER_KEY)?.unwrap_or(default.jitter);
+
+        let policy = Self {
+            max_attempts,
+            min_backoff,
+            max_backoff,
+            factor,
+            jitter,
+        };
+        policy.validate()?;
+        Ok(policy)
+    }
+
+    /// 校验策略参数是否合法
+    pub fn validate(&self) -> Result<()> {
+        if self.max_attempts == 0 {
+            return Err(Error::new_config(&format!(
+                "'{}' 至少为 1",
+                RETRY_MAX_ATTEMPTS_KEY
+            )));
+        }
+        // OpenDAL 在倍数小于 1 时会直接 panic，这里提前拦截
+        if self.factor.is_nan() || self.factor < 1.0 {
+            return Err(Error::new_config(&format!(
+                "'{}' 不能小于 1",
+                RETRY_BACKOFF_FACTOR_KEY
+            )));
+        }
+        if self.min_backoff > self.max_backoff {
+            return Err(Error::new_config(&format!(
+                "'{}' 不能大于 '{}'",
+                RETRY_MIN_BACKOFF_MS_KEY, RETRY_MAX_BACKOFF_MS_KEY
+            )));
+        }
+        Ok(())
+    }
+
+    /// 第 `retry` 次重试（从 1 开始）前的等待时间，与重试层的指数退避方式相同
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as usize) as i32;
+        let base = self.min_backoff.as_secs_f64() * f64::from(self.factor).powi(exponent);
+        let mut delay = Duration::from_secs_f64(base.min(self.max_backoff.as_secs_f64()));
+        if self.jitter {
+            let random = (uuid::Uuid::new_v4().as_u128() % 1000) as f64 / 1000.0;
+            delay += self.min_backoff.mul_f64(random);
+        }
+        delay
+    }
+
+    /// 重试前输出警告日志，与重试层的日志格式一致
+    pub fn notify(service: &str, err: &dyn std::fmt::Display, delay: Duration) {
+        warn!(
+            "{} 操作失败，{:.1} 秒后重试: {}",
+            service,
+            delay.as_secs_f64(),
+            err
+        );
+    }
+
+    /// 为操作符挂载重试层，重试时输出警告日志
+    pub fn apply(&self, operator: Operator, service: &str) -> Operator {
+        if self.max_attempts <= 1 {
+            debug!("{} 操作符未启用重试", service);
+            return operator;
+        }
+
+        debug!(
+            "{} 操作符启用重试: 最多 {} 次尝试, 退避 {:?} ~ {:?}, 倍数 {}, 抖动 {}",
+            service,
+            self.max_attempts,
+            self.min_backoff,
+            self.max_backoff,
+            self.factor,
+            self.jitter
+        );
+
+        let service = service.to_string();
+        let mut layer = RetryLayer::new()
+            .with_max_times(self.max_attempts - 1)
+            .with_min_delay(self.min_backoff)
+            .with_max_delay(self.max_backoff)
+            .with_factor(self.factor);
+        if self.jitter {
+            layer = layer.with_jitter();
+        }
+        let layer = layer.with_notify(move |err: &opendal::Error, dur: Duration| {
+            Self::notify(&service, err, dur);
+        });
+
+        operator.layer(layer)
+    }
+}
+
+pub(crate) fn parse_value<T: FromStr>(
+    config: &HashMap<String, String>,
+    key: &str,
+) -> Result<Option<T>> {
+    match config.get(key).map(|v| v.trim()) {
+        None | Some("") => Ok(None),
+        Some(value) => value
+            .parse::<T>()
+            .map(Some)
+            .map_err(|_| Error::new_config(&format!("配置项 '{}' 的值无效: {}", key, value))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opendal::services;
+
+    fn config(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_config_uses_defaults() {
+        let policy = RetryPolicy::from_config(&HashMap::new()).unwrap();
+        assert_eq!(policy, RetryPolicy::default());
+    }
+
+    #[test]
+    fn test_from_config_overrides() {
+        let policy = RetryPolicy::from_config(&config(&[
+            (RETRY_MAX_ATTEMPTS_KEY, "6"),
+            (RETRY_MIN_BACKOFF_MS_KEY, "200"),
+            (RETRY_MAX_BACKOFF_MS_KEY, "5000"),
+            (RETRY_BACKOFF_FACTOR_KEY, "1.5"),
+            (RETRY_JITTER_KEY, "false"),
+        ]))
+        .unwrap();
+
+        assert_eq!(policy.max_attempts, 6);
+        assert_eq!(policy.min_backoff, Duration::from_millis(200));
+        assert_eq!(policy.max_backoff, Duration::from_secs(5));
+        assert_eq!(policy.factor, 1.5);
+        assert!(!policy.jitter);
+    }
+
+    #[test]
+    fn test_from_config_rejects_invalid_values() {
+        assert!(RetryPolicy::from_config(&config(&[(RETRY_MAX_ATTEMPTS_KEY, "abc")])).is_err());
+        assert!(RetryPolicy::from_config(&config(&[(RETRY_MAX_ATTEMPTS_KEY, "0")])).is_err());
+        assert!(RetryPolicy::from_config(&config(&[(RETRY_BACKOFF_FACTOR_KEY, "0.5")])).is_err());
+        assert!(RetryPolicy::from_config(&config(&[
+            (RETRY_MIN_BACKOFF_MS_KEY, "5000"),
+            (RETRY_MAX_BACKOFF_MS_KEY, "100"),
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy::default()
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500))
+            .with_jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(10), Duration::from_millis(500));
+
+        let jittered = policy.with_jitter(true).backoff(1);
+        assert!(jittered >= Duration::from_millis(100) && jittered < Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_apply_keeps_operator_usable() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let op = RetryPolicy::default().apply(op, "memory");
+        op.write("a.txt", "hello").await.unwrap();
+        assert_eq!(op.read("a.txt").await.unwrap().to_vec(), b"hello");
+
+        let op = RetryPolicy::disabled().apply(op, "memory");
+        assert!(op.exists("a.txt").await.unwrap());
+    }
+}
@@ -0,0 +1,940 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::Utc;
+use futures::{stream, StreamExt};
+use log::{debug, info};
+use reqwest::header::HeaderMap;
+use reqwest::{Method, Response, Url};
+use serde::{Deserialize, Serialize};
+
+use super::credentials::S3Auth;
+use super::signer::{sha256_hex, uri_encode, S3Credentials, SigV4Signer, EMPTY_PAYLOAD_SHA256};
+use crate::core::error::{Error, ErrorKind, Result, EXPIRED_TOKEN_CODES};
+use crate::protocols::retry::RetryPolicy;
+
+/// 已上传完成的分片
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompletedPart {
+    pub part_number: u32,
+    pub etag: String,
+    pub size: u64,
+}
+
+/// 服务端尚未完成的分片上传
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipartUploadInfo {
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: String,
+}
+
+/// 补全 bucket 区域时同时进行的 GetBucketLocation 请求数
+const DISCOVER_CONCURRENCY: usize = 8;
+
+/// 凭证可见的 bucket
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BucketInfo {
+    pub name: String,
+    pub creation_date: Option<String>,
+    /// 所在区域，服务端未返回时为空
+    pub region: Option<String>,
+}
+
+/// HEAD bucket 得到的 bucket 状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BucketStatus {
+    /// bucket 存在且当前凭证可以访问
+    Accessible,
+    /// bucket 存在但位于其他区域，需要使用对应区域的端点访问
+    WrongRegion,
+    /// bucket 存在但当前凭证无权访问（或属于其他账户）
+    Forbidden,
+    NotFound,
+}
+
+impl BucketStatus {
+    /// bucket 是否存在（无论能否访问）
+    pub fn exists(&self) -> bool {
+        *self != BucketStatus::NotFound
+    }
+}
+
+/// HEAD bucket 的结果
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BucketHead {
+    pub status: BucketStatus,
+    /// 响应头 x-amz-bucket-region 给出的区域
+    pub region: Option<String>,
+}
+
+/// 直接调用 S3 REST API 的轻量客户端，用于 OpenDAL 未暴露的操作（如分片上传会话管理、bucket 管理）
+#[derive(Debug, Clone)]
+pub struct S3Client {
+    http: reqwest::Client,
+    endpoint: Url,
+    region: String,
+    bucket: String,
+    path_style: bool,
+    /// 匿名访问时为空，请求不签名；克隆的客户端共享刷新后的凭证
+    signer: Arc<RwLock<Option<SigV4Signer>>>,
+    /// 网络错误、5xx 与限流响应的重试策略
+    retry: RetryPolicy,
+    /// 临时凭证过期时用于重新解析凭证，静态凭证为空
+    refresh: Option<S3Auth>,
+}
+
+impl S3Client {
+    /// 创建新的 S3 客户端，未指定端点时使用 AWS 区域默认端点；凭证为空时发送匿名请求
+    pub fn new(
+        endpoint: Option<&str>,
+        region: &str,
+        bucket: &str,
+        path_style: bool,
+        credentials: Option<S3Credentials>,
+    ) -> Result<Self> {
+        let endpoint = match endpoint {
+            Some(endpoint) => endpoint.trim_end_matches('/').to_string(),
+            None => format!("https://s3.{}.amazonaws.com", region),
+        };
+        let endpoint = Url::parse(&endpoint)
+            .map_err(|e| Error::new_config(&format!("无效的 S3 端点 {}: {}", endpoint, e)))?;
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            endpoint,
+            region: region.to_string(),
+            bucket: bucket.to_string(),
+            path_style,
+            signer: Arc::new(RwLock::new(
+                credentials.map(|credentials| SigV4Signer::new(credentials, region)),
+            )),
+            retry: RetryPolicy::default(),
+            refresh: None,
+        })
+    }
+
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置凭证配置，服务端返回 ExpiredToken 时据此重新解析凭证并重发请求
+    ///
+    /// 只有会变化的凭证来源（环境变量、profile、扮演角色）才需要刷新。
+    pub fn with_credential_refresh(mut self, auth: S3Auth) -> Self {
+        self.refresh = auth.is_refreshable().then_some(auth);
+        self
+    }
+
+    /// 获取客户端所在区域
+    pub fn region(&self) -> &str {
+        &self.region
+    }
+
+    /// 获取 bucket 名称
+    pub fn bucket(&self) -> &str {
+        &self.bucket
+    }
+
+    /// 发起分片上传，返回 UploadId
+    pub async fn create_multipart_upload(&self, key: &str) -> Result<String> {
+        let url = self.object_url(key, &[("uploads", None)])?;
+        let response = self.send(Method::POST, url, Vec::new()).await?;
+        let result: InitiateMultipartUploadResult = parse_xml(&response.text().await?)?;
+
+        debug!("已创建分片上传: {} ({})", key, result.upload_id);
+        Ok(result.upload_id)
+    }
+
+    /// 上传单个分片，返回分片的 ETag
+    pub async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+        body: Vec<u8>,
+    ) -> Result<CompletedPart> {
+        let part = part_number.to_string();
+        let url = self.object_url(
+            key,
+            &[("partNumber", Some(&part)), ("uploadId", Some(upload_id))],
+        )?;
+        let size = body.len() as u64;
+        let response = self.send(Method::PUT, url, body).await?;
+
+        let etag = response
+            .headers()
+            .get("etag")
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| Error::new_protocol("上传分片的响应缺少 ETag"))?
+            .to_string();
+
+        Ok(CompletedPart {
+            part_number,
+            etag,
+            size,
+        })
+    }
+
+    /// 合并所有分片，完成上传
+    pub async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[CompletedPart],
+    ) -> Result<()> {
+        let url = self.object_url(key, &[("uploadId", Some(upload_id))])?;
+
+        let mut parts = parts.to_vec();
+        parts.sort_by_key(|p| p.part_number);
+        let mut body = String::from("<CompleteMultipartUpload>");
+        for part in &parts {
+            body.push_str(&format!(
+                "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag></Part>",
+                part.part_number,
+                escape_xml(&part.etag)
+            ));
+        }
+        body.push_str("</CompleteMultipartUpload>");
+
+        let response = self.send(Method::POST, url, body.into_bytes()).await?;
+
+        // CompleteMultipartUpload 可能在 200 响应中返回错误
+        let text = response.text().await?;
+        if text.contains("<Error>") {
+            return Err(error_from_body(200, &text));
+        }
+
+        debug!("分片上传已完成: {} ({})", key, upload_id);
+        Ok(())
+    }
+
+    /// 中止分片上传，服务端会清理已上传的分片
+    pub async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<()> {
+        let url = self.object_url(key, &[("uploadId", Some(upload_id))])?;
+        self.send(Method::DELETE, url, Vec::new()).await?;
+
+        debug!("已中止分片上传: {} ({})", key, upload_id);
+        Ok(())
+    }
+
+    /// 列出某个分片上传中已上传的分片
+    pub async fn list_parts(&self, key: &str, upload_id: &str) -> Result<Vec<CompletedPart>> {
+        let mut parts = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let mut query = vec![("uploadId", Some(upload_id))];
+            if let Some(marker) = &marker {
+                query.push(("part-number-marker", Some(marker.as_str())));
+            }
+            let url = self.object_url(key, &query)?;
+            let response = self.send(Method::GET, url, Vec::new()).await?;
+            let result: ListPartsResult = parse_xml(&response.text().await?)?;
+
+            parts.extend(result.parts.into_iter().map(|p| CompletedPart {
+                part_number: p.part_number,
+                etag: p.etag,
+                size: p.size,
+            }));
+
+            match (result.is_truncated, result.next_part_number_marker) {
+                (Some(true), Some(next)) => marker = Some(next),
+                _ => break,
+            }
+        }
+
+        Ok(parts)
+    }
+
+    /// 列出 bucket 中尚未完成的分片上传
+    pub async fn list_multipart_uploads(&self, prefix: &str) -> Result<Vec<MultipartUploadInfo>> {
+        let mut uploads = Vec::new();
+        let mut key_marker: Option<String> = None;
+        let mut upload_id_marker: Option<String> = None;
+
+        loop {
+            let mut query = vec![("uploads", None)];
+            if !prefix.is_empty() {
+                query.push(("prefix", Some(prefix)));
+            }
+            if let Some(marker) = &key_marker {
+                query.push(("key-marker", Some(marker.as_str())));
+            }
+            if let Some(marker) = &upload_id_marker {
+                query.push(("upload-id-marker", Some(marker.as_str())));
+            }
+            let url = self.object_url("", &query)?;
+            let response = self.send(Method::GET, url, Vec::new()).await?;
+            let result: ListMultipartUploadsResult = parse_xml(&response.text().await?)?;
+
+            uploads.extend(result.uploads.into_iter().map(|u| MultipartUploadInfo {
+                key: u.key,
+                upload_id: u.upload_id,
+                initiated: u.initiated,
+            }));
+
+            if result.is_truncated != Some(true) {
+                break;
+            }
+            key_marker = result.next_key_marker;
+            upload_id_marker = result.next_upload_id_marker;
+            if key_marker.is_none() {
+                break;
+            }
+        }
+
+        Ok(uploads)
+    }
+
+    /// 列出当前凭证可见的所有 bucket（ListBuckets）
+    pub async fn list_buckets(&self) -> Result<Vec<BucketInfo>> {
+        let mut buckets = Vec::new();
+        let mut continuation_token: Option<String> = None;
+
+        loop {
+            let mut query = Vec::new();
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token", Some(token.as_str())));
+            }
+            let url = self.url(None, "", &query)?;
+            let response = self.send(Method::GET, url, Vec::new()).await?;
+            let result: ListAllMyBucketsResult = parse_xml(&response.text().await?)?;
+
+            buckets.extend(result.buckets.buckets.into_iter().map(|b| BucketInfo {
+                name: b.name,
+                creation_date: b.creation_date,
+                region: b.bucket_region,
+            }));
+
+            match result.continuation_token {
+                Some(token) if !token.is_empty() => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+
+        Ok(buckets)
+    }
+
+    /// 查询 bucket 所在区域（GetBucketLocation）
+    pub async fn bucket_location(&self, bucket: &str) -> Result<String> {
+        let url = self.url(Some(bucket), "", &[("location", None)])?;
+        let response = self.send(Method::GET, url, Vec::new()).await?;
+        let location: LocationConstraintXml = parse_xml(&response.text().await?)?;
+
+        // 历史原因：us-east-1 返回空值，eu-west-1 可能返回 "EU"
+        Ok(match location.region.trim() {
+            "" => "us-east-1".to_string(),
+            "EU" => "eu-west-1".to_string(),
+            region => region.to_string(),
+        })
+    }
+
+    /// 列出凭证可见的 bucket 并补全所在区域
+    ///
+    /// ListBuckets 未返回区域时逐个查询 GetBucketLocation，查询失败的 bucket 区域留空。
+    pub async fn discover_buckets(&self) -> Result<Vec<BucketInfo>> {
+        let buckets = self.list_buckets().await?;
+
+        Ok(stream::iter(buckets)
+            .map(|mut bucket| async move {
+                if bucket.region.is_none() {
+                    match self.bucket_location(&bucket.name).await {
+                        Ok(region) => bucket.region = Some(region),
+                        Err(e) => debug!("无法获取 bucket {} 的区域: {}", bucket.name, e),
+                    }
+                }
+                bucket
+            })
+            .buffered(DISCOVER_CONCURRENCY)
+            .collect()
+            .await)
+    }
+
+    /// 查询 bucket 状态（HeadBucket），区分不存在、无权限和区域不匹配
+    pub async fn head_bucket(&self, bucket: &str) -> Result<BucketHead> {
+        let url = self.url(Some(bucket), "", &[])?;
+        let response = self.send_unchecked(Method::HEAD, url, Vec::new()).await?;
+
+        let region = response
+            .headers()
+            .get("x-amz-bucket-region")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let status = match response.status().as_u16() {
+            200..=299 => BucketStatus::Accessible,
+            301 | 307 => BucketStatus::WrongRegion,
+            // 签名区域不对时部分服务返回 400 并附带正确的区域
+            400 if region.as_deref().is_some_and(|r| r != self.region) => BucketStatus::WrongRegion,
+            403 => BucketStatus::Forbidden,
+            404 => BucketStatus::NotFound,
+            // HEAD 响应没有正文，只能根据状态码构造错误
+            status => return Err(error_from_body(status, "")),
+        };
+
+        debug!("bucket {} 状态: {:?} (区域 {:?})", bucket, status, region);
+        Ok(BucketHead { status, region })
+    }
+
+    /// 在客户端所在区域创建 bucket（CreateBucket）
+    pub async fn create_bucket(&self, bucket: &str) -> Result<()> {
+        let url = self.url(Some(bucket), "", &[])?;
+
+        // us-east-1 是默认区域，不能显式指定 LocationConstraint
+        let body = if self.region == "us-east-1" {
+            Vec::new()
+        } else {
+            format!(
+                "<CreateBucketConfiguration xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+                 <LocationConstraint>{}</LocationConstraint>\
+                 </CreateBucketConfiguration>",
+                escape_xml(&self.region)
+            )
+            .into_bytes()
+        };
+        self.send(Method::PUT, url, body).await?;
+
+        debug!("已创建 bucket: {} ({})", bucket, self.region);
+        Ok(())
+    }
+
+    /// 删除 bucket（DeleteBucket），bucket 必须为空
+    pub async fn delete_bucket(&self, bucket: &str) -> Result<()> {
+        let url = self.url(Some(bucket), "", &[])?;
+        self.send(Method::DELETE, url, Vec::new()).await?;
+
+        debug!("已删除 bucket: {}", bucket);
+        Ok(())
+    }
+
+    /// 构造客户端 bucket 中对象请求的 URL
+    fn object_url(&self, key: &str, query: &[(&str, Option<&str>)]) -> Result<Url> {
+        self.url(Some(&self.bucket), key, query)
+    }
+
+    /// 构造请求 URL，根据 path_style 选择路径样式或虚拟主机样式；bucket 为空时为服务级请求
+    fn url(&self, bucket: Option<&str>, key: &str, query: &[(&str, Option<&str>)]) -> Result<Url> {
+        let key = uri_encode(key.trim_start_matches('/'), false);
+        let host = self.endpoint.host_str().unwrap_or_default();
+        let authority = match self.endpoint.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        };
+
+        let mut url = match bucket {
+            None => format!("{}://{}/{}", self.endpoint.scheme(), authority, key),
+            Some(bucket) if self.path_style => format!(
+                "{}://{}/{}/{}",
+                self.endpoint.scheme(),
+                authority,
+                bucket,
+                key
+            ),
+            Some(bucket) => format!(
+                "{}://{}.{}/{}",
+                self.endpoint.scheme(),
+                bucket,
+                authority,
+                key
+            ),
+        };
+
+        if !query.is_empty() {
+            let query: Vec<String> = query
+                .iter()
+                .map(|(k, v)| match v {
+                    Some(v) => format!("{}={}", uri_encode(k, true), uri_encode(v, true)),
+                    None => uri_encode(k, true),
+                })
+                .collect();
+            url.push('?');
+            url.push_str(&query.join("&"));
+        }
+
+        Url::parse(&url).map_err(|e| Error::new_config(&format!("无效的 S3 URL {}: {}", url, e)))
+    }
+
+    /// 签名并发送请求，非 2xx 响应转换为错误
+    ///
+    /// 临时凭证过期时重新解析一次凭证后重发。
+    async fn send(&self, method: Method, url: Url, body: Vec<u8>) -> Result<Response> {
+        let mut refreshed = false;
+        loop {
+            let response = self
+                .send_unchecked(method.clone(), url.clone(), body.clone())
+                .await?;
+            if response.status().is_success() {
+                return Ok(response);
+            }
+
+            let status = response.status().as_u16();
+            let text = response.text().await.unwrap_or_default();
+            match &self.refresh {
+                Some(auth) if !refreshed && is_expired_token(&text) => {
+                    self.refresh_credentials(auth).await?;
+                    refreshed = true;
+                }
+                _ => return Err(error_from_body(status, &text)),
+            }
+        }
+    }
+
+    /// 重新解析凭证并替换签名器
+    async fn refresh_credentials(&self, auth: &S3Auth) -> Result<()> {
+        info!("S3 临时凭证已过期，重新获取凭证");
+        let credentials = auth.resolve(&self.region).await?;
+        *self.signer.write().unwrap_or_else(|e| e.into_inner()) =
+            credentials.map(|credentials| SigV4Signer::new(credentials, &self.region));
+        Ok(())
+    }
+
+    /// 签名并发送请求，由调用方处理响应状态
+    ///
+    /// 网络错误、5xx 与 429 响应按重试策略重试，每次重试都重新签名；
+    /// 最后一次尝试的响应原样返回给调用方。
+    async fn send_unchecked(&self, method: Method, url: Url, body: Vec<u8>) -> Result<Response> {
+        let mut retry = 0;
+        loop {
+            retry += 1;
+            let last_attempt = retry >= self.retry.max_attempts;
+            let result = self
+                .send_once(method.clone(), url.clone(), body.clone())
+                .await;
+            let err = match result {
+                Ok(response) if last_attempt || !is_retryable_status(response.status()) => {
+                    return Ok(response)
+                }
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    let text = response.text().await.unwrap_or_default();
+                    error_from_body(status, &text)
+                }
+                Err(e) if last_attempt => return Err(e),
+                Err(e) => e,
+            };
+            let delay = self.retry.backoff(retry);
+            RetryPolicy::notify("s3", &err, delay);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    async fn send_once(&self, method: Method, url: Url, body: Vec<u8>) -> Result<Response> {
+        let mut headers = HeaderMap::new();
+        if let Some(signer) = self
+            .signer
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+        {
+            let payload_hash = if body.is_empty() {
+                EMPTY_PAYLOAD_SHA256.to_string()
+            } else {
+                sha256_hex(&body)
+            };
+            signer.sign(
+                method.as_str(),
+                &url,
+                &mut headers,
+                &payload_hash,
+                Utc::now(),
+            )?;
+        }
+
+        debug!("S3 请求: {} {}", method, url);
+        Ok(self
+            .http
+            .request(method, url)
+            .headers(headers)
+            .body(body)
+            .send()
+            .await?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct InitiateMultipartUploadResult {
+    upload_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListPartsResult {
+    #[serde(rename = "Part", default)]
+    parts: Vec<PartXml>,
+    is_truncated: Option<bool>,
+    next_part_number_marker: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct PartXml {
+    part_number: u32,
+    #[serde(rename = "ETag")]
+    etag: String,
+    size: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListMultipartUploadsResult {
+    #[serde(rename = "Upload", default)]
+    uploads: Vec<UploadXml>,
+    is_truncated: Option<bool>,
+    next_key_marker: Option<String>,
+    next_upload_id_marker: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct UploadXml {
+    key: String,
+    upload_id: String,
+    initiated: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ListAllMyBucketsResult {
+    #[serde(default)]
+    buckets: BucketsXml,
+    continuation_token: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct BucketsXml {
+    #[serde(rename = "Bucket", default)]
+    buckets: Vec<BucketXml>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BucketXml {
+    name: String,
+    creation_date: Option<String>,
+    bucket_region: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocationConstraintXml {
+    #[serde(rename = "$text", default)]
+    region: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ErrorXml {
+    code: String,
+    message: Option<String>,
+}
+
+pub(crate) fn parse_xml<T: serde::de::DeserializeOwned>(text: &str) -> Result<T> {
+    quick_xml::de::from_str(text)
+        .map_err(|e| Error::new_protocol(&format!("解析 S3 响应失败: {}", e)))
+}
+
+/// 服务端错误与限流（包括以 503 返回的 SlowDown）可以重试
+fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+}
+
+fn is_expired_token(body: &str) -> bool {
+    quick_xml::de::from_str::<ErrorXml>(body)
+        .is_ok_and(|err| EXPIRED_TOKEN_CODES.contains(&err.code.as_str()))
+}
+
+/// 将 S3 错误响应转换为错误，404 类错误映射为未找到，凭证过期视为无权限
+pub(crate) fn error_from_body(status: u16, body: &str) -> Error {
+    let (code, message) = match quick_xml::de::from_str::<ErrorXml>(body) {
+        Ok(err) => (err.code, err.message.unwrap_or_default()),
+        Err(_) => (String::new(), body.trim().to_string()),
+    };
+
+    let text = format!("S3 请求失败 ({}): {} {}", status, code, message);
+    let kind = match (status, code.as_str()) {
+        (404, _) => ErrorKind::NotFound,
+        (_, c) if c.starts_with("NoSuch") => ErrorKind::NotFound,
+        (403, _) => ErrorKind::PermissionDenied,
+        (_, c) if EXPIRED_TOKEN_CODES.contains(&c) => ErrorKind::PermissionDenied,
+        (409, "BucketAlreadyExists" | "BucketAlreadyOwnedByYou") => ErrorKind::AlreadyExists,
+        (429 | 503, _) | (_, "SlowDown") => ErrorKind::RateLimited,
+        _ => ErrorKind::Protocol,
+    };
+    Error::new(kind, text.trim())
+}
+
+fn escape_xml(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_client(endpoint: Option<&str>, path_style: bool) -> S3Client {
+        S3Client::new(
+            endpoint,
+            "us-east-1",
+            "my-bucket",
+            path_style,
+            Some(S3Credentials {
+                access_key: "ak".to_string(),
+                secret_key: "sk".to_string(),
+                session_token: None,
+            }),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_object_url_virtual_host_style() {
+        let client = test_client(None, false);
+        let url = client.object_url("/dir/a b.txt", &[]).unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://my-bucket.s3.us-east-1.amazonaws.com/dir/a%20b.txt"
+        );
+    }
+
+    #[test]
+    fn test_object_url_path_style_with_query() {
+        let client = test_client(Some("http://127.0.0.1:9000/"), true);
+        let url = client
+            .object_url("file.bin", &[("uploads", None), ("prefix", Some("a/b"))])
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "http://127.0.0.1:9000/my-bucket/file.bin?uploads&prefix=a%2Fb"
+        );
+    }
+
+    #[test]
+    fn test_bucket_and_service_urls() {
+        let client = test_client(None, false);
+        assert_eq!(
+            client.url(None, "", &[]).unwrap().as_str(),
+            "https://s3.us-east-1.amazonaws.com/"
+        );
+        assert_eq!(
+            client.url(Some("other"), "", &[]).unwrap().as_str(),
+            "https://other.s3.us-east-1.amazonaws.com/"
+        );
+
+        let client = test_client(Some("http://127.0.0.1:9000"), true);
+        assert_eq!(
+            client.url(Some("other"), "", &[]).unwrap().as_str(),
+            "http://127.0.0.1:9000/other/"
+        );
+    }
+
+    /// 依次用给定的响应应答每个连接，返回服务地址与收到的请求数
+    async fn serve_responses(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        tokio::spawn(async move {
+            for (status, body) in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        (endpoint, requests)
+    }
+
+    fn fast_retry(max_attempts: usize) -> RetryPolicy {
+        RetryPolicy::default()
+            .with_max_attempts(max_attempts)
+            .with_backoff(
+                std::time::Duration::from_millis(1),
+                std::time::Duration::from_millis(1),
+            )
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_slow_down_and_server_errors() {
+        let (endpoint, requests) = serve_responses(vec![
+            (503, "<Error><Code>SlowDown</Code></Error>"),
+            (500, "<Error><Code>InternalError</Code></Error>"),
+            (
+                200,
+                "<ListAllMyBucketsResult><Buckets><Bucket><Name>logs</Name></Bucket></Buckets></ListAllMyBucketsResult>",
+            ),
+        ])
+        .await;
+
+        let client = test_client(Some(&endpoint), true).with_retry(fast_retry(4));
+        let buckets = client.list_buckets().await.unwrap();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_gives_up_after_max_attempts() {
+        let (endpoint, requests) = serve_responses(vec![
+            (503, "<Error><Code>SlowDown</Code></Error>"),
+            (503, "<Error><Code>SlowDown</Code></Error>"),
+        ])
+        .await;
+
+        let client = test_client(Some(&endpoint), true).with_retry(fast_retry(2));
+        let err = client.list_buckets().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // 客户端错误不重试
+        let (endpoint, requests) =
+            serve_responses(vec![(403, "<Error><Code>AccessDenied</Code></Error>")]).await;
+        let client = test_client(Some(&endpoint), true).with_retry(fast_retry(4));
+        let err = client.list_buckets().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_refreshes_expired_credentials() {
+        use super::super::credentials::CredentialSource;
+
+        let expired = "<Error><Code>ExpiredToken</Code><Message>The provided token has expired.</Message></Error>";
+        let listed = "<ListAllMyBucketsResult><Buckets></Buckets></ListAllMyBucketsResult>";
+
+        // 环境变量凭证过期后重新读取并重发一次
+        std::env::set_var("AWS_ACCESS_KEY_ID", "refreshed-ak");
+        std::env::set_var("AWS_SECRET_ACCESS_KEY", "refreshed-sk");
+        let (endpoint, requests) = serve_responses(vec![(400, expired), (200, listed)]).await;
+        let client = test_client(Some(&endpoint), true)
+            .with_credential_refresh(S3Auth::new(CredentialSource::Env));
+        assert!(client.list_buckets().await.unwrap().is_empty());
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // 静态凭证无法刷新，直接返回无权限错误
+        let (endpoint, requests) = serve_responses(vec![(400, expired)]).await;
+        let client = test_client(Some(&endpoint), true).with_credential_refresh(S3Auth::new(
+            CredentialSource::Static {
+                access_key: "ak".to_string(),
+                secret_key: "sk".to_string(),
+                session_token: None,
+            },
+        ));
+        let err = client.list_buckets().await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_parse_list_buckets() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListAllMyBucketsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Owner><ID>owner</ID><DisplayName>me</DisplayName></Owner>
+              <Buckets>
+                <Bucket>
+                  <Name>logs</Name>
+                  <CreationDate>2024-01-01T00:00:00.000Z</CreationDate>
+                  <BucketRegion>eu-west-1</BucketRegion>
+                </Bucket>
+                <Bucket><Name>photos</Name></Bucket>
+              </Buckets>
+            </ListAllMyBucketsResult>"#;
+
+        let result: ListAllMyBucketsResult = parse_xml(xml).unwrap();
+        assert_eq!(result.buckets.buckets.len(), 2);
+        assert_eq!(result.buckets.buckets[0].name, "logs");
+        assert_eq!(
+            result.buckets.buckets[0].bucket_region.as_deref(),
+            Some("eu-west-1")
+        );
+        assert!(result.buckets.buckets[1].creation_date.is_none());
+        assert!(result.continuation_token.is_none());
+
+        let empty: ListAllMyBucketsResult =
+            parse_xml("<ListAllMyBucketsResult><Buckets></Buckets></ListAllMyBucketsResult>")
+                .unwrap();
+        assert!(empty.buckets.buckets.is_empty());
+    }
+
+    #[test]
+    fn test_parse_location_constraint() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/">eu-west-1</LocationConstraint>"#;
+        let location: LocationConstraintXml = parse_xml(xml).unwrap();
+        assert_eq!(location.region, "eu-west-1");
+
+        // us-east-1 的 bucket 返回空元素
+        let xml = r#"<LocationConstraint xmlns="http://s3.amazonaws.com/doc/2006-03-01/"/>"#;
+        let location: LocationConstraintXml = parse_xml(xml).unwrap();
+        assert_eq!(location.region, "");
+    }
+
+    #[test]
+    fn test_parse_list_multipart_uploads() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <ListMultipartUploadsResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Bucket>my-bucket</Bucket>
+              <IsTruncated>false</IsTruncated>
+              <Upload>
+                <Key>backup.tar</Key>
+                <UploadId>abc123</UploadId>
+                <Initiated>2024-01-01T00:00:00.000Z</Initiated>
+              </Upload>
+            </ListMultipartUploadsResult>"#;
+
+        let result: ListMultipartUploadsResult = parse_xml(xml).unwrap();
+        assert_eq!(result.uploads.len(), 1);
+        assert_eq!(result.uploads[0].key, "backup.tar");
+        assert_eq!(result.uploads[0].upload_id, "abc123");
+        assert_eq!(result.is_truncated, Some(false));
+    }
+
+    #[test]
+    fn test_parse_list_parts_empty() {
+        let xml = r#"<ListPartsResult><IsTruncated>false</IsTruncated></ListPartsResult>"#;
+        let result: ListPartsResult = parse_xml(xml).unwrap();
+        assert!(result.parts.is_empty());
+    }
+
+    #[test]
+    fn test_parse_list_parts() {
+        let xml = r#"<ListPartsResult>
+              <Part><PartNumber>1</PartNumber><ETag>"e1"</ETag><Size>5242880</Size></Part>
+              <Part><PartNumber>2</PartNumber><ETag>"e2"</ETag><Size>100</Size></Part>
+            </ListPartsResult>"#;
+        let result: ListPartsResult = parse_xml(xml).unwrap();
+        assert_eq!(result.parts.len(), 2);
+        assert_eq!(result.parts[1].etag, "\"e2\"");
+        assert_eq!(result.parts[1].size, 100);
+    }
+
+    #[test]
+    fn test_error_from_body() {
+        let xml = "<Error><Code>NoSuchUpload</Code><Message>gone</Message></Error>";
+        let err = error_from_body(404, xml);
+        assert!(err.to_string().contains("NoSuchUpload"));
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        let xml = "<Error><Code>AccessDenied</Code><Message>denied</Message></Error>";
+        assert_eq!(
+            error_from_body(403, xml).kind(),
+            ErrorKind::PermissionDenied
+        );
+    }
+}
@@ -0,0 +1,461 @@
+pub mod client;
+pub mod credentials;
+pub mod multipart;
+pub mod signer;
+
+use std::collections::HashMap;
+
+use futures::TryStreamExt;
+use log::{debug, info};
+use opendal::{services, Operator};
+
+use super::limits::{apply_layers, ConnectionLimits};
+use super::retry::RetryPolicy;
+use super::traits::{Capabilities, Protocol};
+use crate::core::error::{Error, Result};
+use client::{BucketInfo, S3Client};
+use credentials::{CredentialSource, S3Auth};
+
+/// S3 请求的寻址方式
+///
+/// - 路径样式：`https://endpoint/bucket/key`，MinIO、Ceph 等自建服务通常只支持这种方式
+/// - 虚拟主机样式：`https://bucket.endpoint/key`，AWS 推荐的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressingStyle {
+    /// 根据端点推断：自定义端点或 bucket 名含 '.' 时用路径样式，否则用虚拟主机样式
+    Auto,
+    Path,
+    VirtualHost,
+}
+
+impl AddressingStyle {
+    /// 连接配置中的键
+    pub const CONFIG_KEY: &'static str = "addressing_style";
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "auto" => Ok(Self::Auto),
+            "path" => Ok(Self::Path),
+            "virtual_host" | "virtual" => Ok(Self::VirtualHost),
+            other => Err(Error::new_config(&format!(
+                "无效的 S3 寻址方式: {}（可选 auto、path、virtual_host）",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Path => "path",
+            Self::VirtualHost => "virtual_host",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct S3Protocol {
+    bucket: String,
+    region: String,
+    endpoint: Option<String>,
+    auth: S3Auth,
+    addressing_style: AddressingStyle,
+    retry: RetryPolicy,
+    limits: ConnectionLimits,
+}
+
+impl S3Protocol {
+    pub fn new(
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+        endpoint: Option<String>,
+        path_style: bool,
+    ) -> Self {
+        Self {
+            bucket,
+            region,
+            endpoint,
+            auth: S3Auth::new(CredentialSource::Static {
+                access_key,
+                secret_key,
+                session_token: None,
+            }),
+            addressing_style: if path_style {
+                AddressingStyle::Path
+            } else {
+                AddressingStyle::VirtualHost
+            },
+            retry: RetryPolicy::default(),
+            limits: ConnectionLimits::default(),
+        }
+    }
+
+    /// 设置凭证来源
+    pub fn with_auth(mut self, auth: S3Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// 设置寻址方式
+    pub fn with_addressing_style(mut self, addressing_style: AddressingStyle) -> Self {
+        self.addressing_style = addressing_style;
+        self
+    }
+
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置超时与并发限制
+    pub fn with_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let bucket = config
+            .get("bucket")
+            .ok_or_else(|| Error::new_config("S3配置缺少 'bucket' 参数"))?
+            .clone();
+
+        let region = config
+            .get("region")
+            .ok_or_else(|| Error::new_config("S3配置缺少 'region' 参数"))?
+            .clone();
+
+        // 界面清空端点时会保存空字符串，视为使用 AWS 默认端点
+        let endpoint = config
+            .get("endpoint")
+            .filter(|e| !e.trim().is_empty())
+            .cloned();
+
+        // 优先使用 addressing_style，兼容旧配置中的 path_style 开关，均未配置时自动推断
+        let addressing_style = match (
+            config.get(AddressingStyle::CONFIG_KEY),
+            config.get("path_style"),
+        ) {
+            (Some(style), _) => AddressingStyle::parse(style)?,
+            (None, Some(path_style)) if path_style.eq_ignore_ascii_case("true") => {
+                AddressingStyle::Path
+            }
+            (None, Some(path_style)) if path_style.eq_ignore_ascii_case("false") => {
+                AddressingStyle::VirtualHost
+            }
+            _ => AddressingStyle::Auto,
+        };
+
+        Ok(Self {
+            bucket,
+            region,
+            endpoint,
+            auth: S3Auth::from_config(config)?,
+            addressing_style,
+            retry: RetryPolicy::from_config(config)?,
+            limits: ConnectionLimits::from_config(config)?,
+        })
+    }
+
+    /// 当前配置实际使用的是否为路径样式
+    pub fn uses_path_style(&self) -> bool {
+        match self.addressing_style {
+            AddressingStyle::Path => true,
+            AddressingStyle::VirtualHost => false,
+            // 虚拟主机样式下 bucket 名中的 '.' 会导致 TLS 证书不匹配
+            AddressingStyle::Auto => self.endpoint.is_some() || self.bucket.contains('.'),
+        }
+    }
+
+    /// 依次尝试两种寻址方式访问 bucket，返回第一种可用的方式
+    ///
+    /// 用于测试连接时确定自建服务支持的寻址方式；两种都失败时返回首选方式的错误。
+    pub async fn detect_addressing_style(&self) -> Result<AddressingStyle> {
+        let preferred = if self.uses_path_style() {
+            AddressingStyle::Path
+        } else {
+            AddressingStyle::VirtualHost
+        };
+        let fallback = if preferred == AddressingStyle::Path {
+            AddressingStyle::VirtualHost
+        } else {
+            AddressingStyle::Path
+        };
+
+        let mut first_error = None;
+        for style in [preferred, fallback] {
+            let probe = Self {
+                addressing_style: style,
+                // 探测只需要快速得到结果，不做重试
+                retry: RetryPolicy::disabled(),
+                ..self.clone()
+            };
+            let operator = probe.create_operator()?;
+            let result = async {
+                let mut lister = operator.lister("/").await?;
+                lister.try_next().await?;
+                Ok::<_, opendal::Error>(())
+            }
+            .await;
+
+            match result {
+                Ok(()) => {
+                    info!("S3 bucket {} 使用{}访问成功", self.bucket, style.as_str());
+                    return Ok(style);
+                }
+                Err(e) => {
+                    debug!("S3 寻址方式 {} 探测失败: {}", style.as_str(), e);
+                    first_error.get_or_insert(Error::from(e));
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or_else(|| Error::new_other("无法确定 S3 寻址方式")))
+    }
+
+    /// 创建直接访问 S3 REST API 的客户端（分片上传会话管理等 OpenDAL 未覆盖的操作）
+    ///
+    /// 配置了角色时会先通过 STS 换取临时凭证，凭证过期后由客户端重新换取；匿名访问时请求不签名。
+    pub async fn create_client(&self) -> Result<S3Client> {
+        let credentials = self.auth.resolve(&self.region).await?;
+        Ok(S3Client::new(
+            self.endpoint.as_deref(),
+            &self.region,
+            &self.bucket,
+            self.uses_path_style(),
+            credentials,
+        )?
+        .with_retry(self.retry.clone())
+        .with_credential_refresh(self.auth.clone()))
+    }
+}
+
+/// 创建不绑定 bucket 的客户端，用于列出、创建、删除 bucket 等服务级操作
+///
+/// 配置中的 bucket 可以省略，其余项（区域、端点、凭证、寻址方式）与连接配置相同。
+pub async fn create_service_client(config: &HashMap<String, String>) -> Result<S3Client> {
+    let mut config = config.clone();
+    config.entry("bucket".to_string()).or_default();
+    S3Protocol::from_config(&config)?.create_client().await
+}
+
+/// 以发现的 bucket 为目标生成连接配置，区域使用 bucket 实际所在的区域
+pub fn bucket_config(
+    config: &HashMap<String, String>,
+    bucket: &BucketInfo,
+) -> HashMap<String, String> {
+    let mut config = config.clone();
+    config.insert("bucket".to_string(), bucket.name.clone());
+    if let Some(region) = &bucket.region {
+        config.insert("region".to_string(), region.clone());
+    }
+    config
+}
+
+impl Protocol for S3Protocol {
+    fn create_operator(&self) -> Result<Operator> {
+        debug!(
+            "创建 S3 操作符, bucket: {}, region: {}",
+            self.bucket, self.region
+        );
+
+        // 创建 S3 服务配置
+        // 凭证完全由连接配置决定，不让 OpenDAL 再从环境变量或 ~/.aws 补全
+        let mut builder = services::S3::default()
+            .bucket(&self.bucket)
+            .region(&self.region)
+            .disable_config_load();
+
+        match self.auth.base_credentials()? {
+            Some(credentials) => {
+                builder = builder
+                    .access_key_id(&credentials.access_key)
+                    .secret_access_key(&credentials.secret_key);
+                if let Some(token) = &credentials.session_token {
+                    builder = builder.session_token(token);
+                }
+            }
+            None => {
+                debug!("使用匿名访问");
+                builder = builder.allow_anonymous().disable_ec2_metadata();
+            }
+        }
+
+        // OpenDAL 以上面的凭证为源，通过 STS 扮演角色并自动刷新临时凭证
+        if let Some(role) = &self.auth.assume_role {
+            debug!("扮演角色: {}", role.role_arn);
+            builder = builder.role_arn(&role.role_arn);
+            if let Some(external_id) = &role.external_id {
+                builder = builder.external_id(external_id);
+            }
+            if let Some(session_name) = &role.session_name {
+                builder = builder.role_session_name(session_name);
+            }
+        }
+
+        if let Some(endpoint) = &self.endpoint {
+            debug!("使用自定义端点: {}", endpoint);
+            builder = builder.endpoint(endpoint);
+        }
+
+        // OpenDAL 默认使用路径样式
+        if self.uses_path_style() {
+            debug!("使用路径样式访问");
+        } else {
+            debug!("使用虚拟主机样式访问");
+            builder = builder.enable_virtual_host_style();
+        }
+
+        // 创建 Operator - 注意需要 finish() 完成初始化
+        let op = match Operator::new(builder) {
+            Ok(op_builder) => op_builder.finish(),
+            Err(e) => return Err(Error::from(e)),
+        };
+
+        Ok(apply_layers(op, &self.retry, &self.limits, "s3"))
+    }
+
+    fn get_id(&self) -> String {
+        format!("s3:{}", self.bucket)
+    }
+
+    fn get_name(&self) -> String {
+        if let Some(endpoint) = &self.endpoint {
+            format!("S3 ({}) @ {}", self.bucket, endpoint)
+        } else {
+            format!("S3 ({}) @ {}", self.bucket, self.region)
+        }
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::default()
+            .with_list(true)
+            .with_read(true)
+            .with_write(true)
+            .with_delete(true)
+            .with_create_dir(true)
+            .with_copy(true)
+            .with_batch_delete(true)
+    }
+
+    fn get_rate_limit(&self) -> Option<u64> {
+        self.limits.rate_limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> HashMap<String, String> {
+        HashMap::from([
+            ("bucket".to_string(), "my-bucket".to_string()),
+            ("region".to_string(), "us-east-1".to_string()),
+            ("access_key".to_string(), "ak".to_string()),
+            ("secret_key".to_string(), "sk".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_addressing_style_auto_detection_rules() {
+        // AWS 默认端点使用虚拟主机样式
+        let protocol = S3Protocol::from_config(&base_config()).unwrap();
+        assert_eq!(protocol.addressing_style, AddressingStyle::Auto);
+        assert!(!protocol.uses_path_style());
+
+        // 自定义端点（MinIO、Ceph 等）使用路径样式
+        let mut config = base_config();
+        config.insert("endpoint".to_string(), "http://127.0.0.1:9000".to_string());
+        assert!(S3Protocol::from_config(&config).unwrap().uses_path_style());
+
+        // 空端点等同于未配置
+        let mut config = base_config();
+        config.insert("endpoint".to_string(), " ".to_string());
+        let protocol = S3Protocol::from_config(&config).unwrap();
+        assert!(protocol.endpoint.is_none());
+        assert!(!protocol.uses_path_style());
+
+        // bucket 名含 '.' 时虚拟主机样式的证书无法匹配
+        let mut config = base_config();
+        config.insert("bucket".to_string(), "my.bucket".to_string());
+        assert!(S3Protocol::from_config(&config).unwrap().uses_path_style());
+    }
+
+    #[test]
+    fn test_addressing_style_from_config() {
+        let mut config = base_config();
+        config.insert("endpoint".to_string(), "http://127.0.0.1:9000".to_string());
+        config.insert("addressing_style".to_string(), "virtual_host".to_string());
+        assert!(!S3Protocol::from_config(&config).unwrap().uses_path_style());
+
+        // 兼容旧的 path_style 开关
+        let mut config = base_config();
+        config.insert("path_style".to_string(), "true".to_string());
+        assert!(S3Protocol::from_config(&config).unwrap().uses_path_style());
+
+        let mut config = base_config();
+        config.insert("addressing_style".to_string(), "sideways".to_string());
+        assert!(S3Protocol::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_bucket_config_uses_bucket_region() {
+        let mut config = base_config();
+        config.remove("bucket");
+
+        let bucket = BucketInfo {
+            name: "logs".to_string(),
+            creation_date: None,
+            region: Some("eu-west-1".to_string()),
+        };
+        let derived = bucket_config(&config, &bucket);
+        assert_eq!(derived["bucket"], "logs");
+        assert_eq!(derived["region"], "eu-west-1");
+        assert_eq!(derived["access_key"], "ak");
+
+        // 区域未知时保留原配置
+        let bucket = BucketInfo {
+            region: None,
+            ..bucket
+        };
+        assert_eq!(bucket_config(&config, &bucket)["region"], "us-east-1");
+    }
+
+    #[tokio::test]
+    async fn test_create_operator_with_both_styles() {
+        for style in [AddressingStyle::Path, AddressingStyle::VirtualHost] {
+            let protocol = S3Protocol::from_config(&base_config())
+                .unwrap()
+                .with_addressing_style(style);
+            assert!(protocol.create_operator().is_ok());
+            assert!(protocol.create_client().await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_client_with_empty_endpoint() {
+        let mut config = base_config();
+        config.insert("endpoint".to_string(), String::new());
+        let client = S3Protocol::from_config(&config)
+            .unwrap()
+            .create_client()
+            .await
+            .unwrap();
+        assert_eq!(client.region(), "us-east-1");
+        assert!(create_service_client(&config).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_anonymous_access() {
+        let mut config = base_config();
+        config.remove("access_key");
+        config.remove("secret_key");
+        config.insert("credential_source".to_string(), "anonymous".to_string());
+
+        let protocol = S3Protocol::from_config(&config).unwrap();
+        assert!(protocol.create_operator().is_ok());
+        assert!(protocol.create_client().await.is_ok());
+    }
+}
@@ -0,0 +1,421 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use futures::{StreamExt, TryStreamExt};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use super::client::{CompletedPart, S3Client};
+use super::signer::sha256_hex;
+use crate::core::error::{Error, Result};
+use crate::core::throttle::Throttle;
+use crate::core::transfer::{ProgressTracker, TransferDirection, TransferProgress};
+
+/// S3 要求的最小分片大小（最后一个分片除外）
+pub const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// S3 允许的最大分片数量
+pub const MAX_PARTS: u64 = 10_000;
+
+/// 默认分片大小
+pub const DEFAULT_PART_SIZE: usize = 16 * 1024 * 1024;
+
+/// 默认并发上传的分片数
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// 分片上传参数
+#[derive(Debug, Clone)]
+pub struct MultipartOptions {
+    pub part_size: usize,
+    pub concurrency: usize,
+}
+
+impl Default for MultipartOptions {
+    fn default() -> Self {
+        Self {
+            part_size: DEFAULT_PART_SIZE,
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+impl MultipartOptions {
+    /// 从连接配置读取 `multipart_part_size`（字节）和 `multipart_concurrency`
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let mut options = Self::default();
+
+        if let Some(value) = config.get("multipart_part_size") {
+            options.part_size = value
+                .parse()
+                .map_err(|_| Error::new_config("S3配置 'multipart_part_size' 无效"))?;
+        }
+
+        if let Some(value) = config.get("multipart_concurrency") {
+            options.concurrency = value
+                .parse()
+                .map_err(|_| Error::new_config("S3配置 'multipart_concurrency' 无效"))?;
+        }
+
+        Ok(options)
+    }
+
+    /// 设置分片大小
+    pub fn with_part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size;
+        self
+    }
+
+    /// 设置并发数
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// 根据文件大小计算实际使用的分片大小，保证不小于最小值且分片数不超过上限
+    pub fn effective_part_size(&self, file_size: u64) -> usize {
+        let min_for_limit = file_size.div_ceil(MAX_PARTS) as usize;
+        self.part_size.max(MIN_PART_SIZE).max(min_for_limit)
+    }
+}
+
+/// 本地持久化的分片上传会话，用于中断后续传
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultipartSession {
+    pub bucket: String,
+    pub key: String,
+    pub upload_id: String,
+    pub local_path: String,
+    pub file_size: u64,
+    pub file_modified: Option<u64>,
+    pub part_size: usize,
+    pub completed_parts: Vec<CompletedPart>,
+}
+
+impl MultipartSession {
+    /// 会话文件路径，由 bucket、对象键和本地文件路径共同决定
+    pub fn state_path(state_dir: &Path, bucket: &str, key: &str, local_path: &str) -> PathBuf {
+        let digest = sha256_hex(format!("{}\n{}\n{}", bucket, key, local_path).as_bytes());
+        state_dir.join(format!("{}.json", &digest[..16]))
+    }
+
+    /// 读取会话文件
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// 保存会话文件
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 列出状态目录中所有的会话
+    pub fn list(state_dir: &Path) -> Vec<(PathBuf, Self)> {
+        let Ok(entries) = std::fs::read_dir(state_dir) else {
+            return Vec::new();
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| Self::load(&path).map(|session| (path, session)))
+            .collect()
+    }
+
+    /// 分片总数
+    pub fn part_count(&self) -> u32 {
+        self.file_size.div_ceil(self.part_size as u64).max(1) as u32
+    }
+
+    /// 本地文件是否与会话记录一致
+    fn matches_file(&self, file_size: u64, file_modified: Option<u64>) -> bool {
+        self.file_size == file_size && self.file_modified == file_modified
+    }
+}
+
+/// S3 分片上传器，支持并发上传分片，并将进度持久化以便中断后续传
+pub struct MultipartUploader<'a> {
+    client: &'a S3Client,
+    options: MultipartOptions,
+    state_dir: PathBuf,
+    throttle: Throttle,
+}
+
+impl<'a> MultipartUploader<'a> {
+    /// 创建分片上传器，会话文件保存在 `state_dir` 中
+    pub fn new(client: &'a S3Client, options: MultipartOptions, state_dir: &Path) -> Self {
+        Self {
+            client,
+            options,
+            state_dir: state_dir.to_path_buf(),
+            throttle: Throttle::default(),
+        }
+    }
+
+    /// 设置上传分片时遵守的带宽限制
+    pub fn with_throttle(mut self, throttle: Throttle) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    /// 上传本地文件；若存在未完成的会话且本地文件未变化，只上传缺失的分片
+    pub async fn upload<F>(
+        &self,
+        local_path: &Path,
+        remote_path: &str,
+        mut progress_callback: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&TransferProgress) + Send + Sync,
+    {
+        let key = remote_path.trim_start_matches('/').to_string();
+        let metadata = std::fs::metadata(local_path).map_err(|_| {
+            Error::new_not_found(&format!("本地文件不存在: {}", local_path.display()))
+        })?;
+        let file_size = metadata.len();
+        let file_modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs());
+        let local = std::fs::canonicalize(local_path)?
+            .to_string_lossy()
+            .to_string();
+
+        let state_path =
+            MultipartSession::state_path(&self.state_dir, self.client.bucket(), &key, &local);
+        let mut session = match self
+            .resume_session(&state_path, file_size, file_modified)
+            .await?
+        {
+            Some(session) => session,
+            None => {
+                let upload_id = self.client.create_multipart_upload(&key).await?;
+                let session = MultipartSession {
+                    bucket: self.client.bucket().to_string(),
+                    key: key.clone(),
+                    upload_id,
+                    local_path: local,
+                    file_size,
+                    file_modified,
+                    part_size: self.options.effective_part_size(file_size),
+                    completed_parts: Vec::new(),
+                };
+                session.save(&state_path)?;
+                session
+            }
+        };
+
+        let done: HashSet<u32> = session
+            .completed_parts
+            .iter()
+            .map(|p| p.part_number)
+            .collect();
+        let pending: Vec<u32> = (1..=session.part_count())
+            .filter(|n| !done.contains(n))
+            .collect();
+
+        let uploaded: u64 = session.completed_parts.iter().map(|p| p.size).sum();
+        let mut tracker =
+            ProgressTracker::new(TransferDirection::Upload, &key, file_size).resume_from(uploaded);
+        progress_callback(&tracker.snapshot());
+
+        info!(
+            "分片上传 {}: 共 {} 个分片，待上传 {} 个 (分片大小 {} 字节，并发 {})",
+            key,
+            session.part_count(),
+            pending.len(),
+            session.part_size,
+            self.options.concurrency
+        );
+
+        let upload_id = session.upload_id.clone();
+        let part_size = session.part_size;
+        let mut parts = futures::stream::iter(pending)
+            .map(|part_number| {
+                self.upload_part(
+                    local_path,
+                    &key,
+                    &upload_id,
+                    part_number,
+                    part_size,
+                    file_size,
+                )
+            })
+            .buffer_unordered(self.options.concurrency.max(1));
+
+        while let Some(part) = parts.try_next().await? {
+            progress_callback(&tracker.advance(part.size));
+            session.completed_parts.push(part);
+            session.save(&state_path)?;
+        }
+
+        self.client
+            .complete_multipart_upload(&key, &session.upload_id, &session.completed_parts)
+            .await?;
+        std::fs::remove_file(&state_path)?;
+
+        info!("分片上传完成: {} -> {}", local_path.display(), key);
+        Ok(())
+    }
+
+    /// 中止服务端的分片上传，并清理对应的本地会话
+    pub async fn abort(&self, key: &str, upload_id: &str) -> Result<()> {
+        self.client.abort_multipart_upload(key, upload_id).await?;
+
+        for (path, session) in MultipartSession::list(&self.state_dir) {
+            if session.upload_id == upload_id {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 尝试恢复已有会话，以服务端实际已上传的分片为准
+    async fn resume_session(
+        &self,
+        state_path: &Path,
+        file_size: u64,
+        file_modified: Option<u64>,
+    ) -> Result<Option<MultipartSession>> {
+        let Some(mut session) = MultipartSession::load(state_path) else {
+            return Ok(None);
+        };
+
+        if !session.matches_file(file_size, file_modified) {
+            info!("本地文件已变化，放弃旧的分片上传: {}", session.upload_id);
+            let _ = self
+                .client
+                .abort_multipart_upload(&session.key, &session.upload_id)
+                .await;
+            return Ok(None);
+        }
+
+        match self
+            .client
+            .list_parts(&session.key, &session.upload_id)
+            .await
+        {
+            Ok(parts) => {
+                let last = session.part_count();
+                let expected = |n: u32| {
+                    if n == last {
+                        session.file_size - (last as u64 - 1) * session.part_size as u64
+                    } else {
+                        session.part_size as u64
+                    }
+                };
+                session.completed_parts = parts
+                    .into_iter()
+                    .filter(|p| p.part_number <= last && p.size == expected(p.part_number))
+                    .collect();
+                info!(
+                    "恢复分片上传 {}: 已完成 {} 个分片",
+                    session.upload_id,
+                    session.completed_parts.len()
+                );
+                Ok(Some(session))
+            }
+            Err(e) => {
+                debug!("无法恢复分片上传 {}: {}", session.upload_id, e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// 读取本地文件中的一个分片并上传
+    async fn upload_part(
+        &self,
+        local_path: &Path,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+        part_size: usize,
+        file_size: u64,
+    ) -> Result<CompletedPart> {
+        let offset = (part_number as u64 - 1) * part_size as u64;
+        let len = (file_size - offset).min(part_size as u64) as usize;
+
+        let mut file = tokio::fs::File::open(local_path).await?;
+        file.seek(std::io::SeekFrom::Start(offset)).await?;
+        let mut body = vec![0u8; len];
+        // 按切片申请额度，并发上传的分片交替获得带宽，而不是整片透支后长时间等待
+        for slice in body.chunks_mut(self.throttle.slice_size(len)) {
+            self.throttle.consume(slice.len() as u64).await;
+            file.read_exact(slice).await?;
+        }
+
+        debug!("上传分片 {} ({} 字节)", part_number, len);
+        self.client
+            .upload_part(key, upload_id, part_number, body)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_effective_part_size() {
+        let options = MultipartOptions::default().with_part_size(1024);
+        // 小于最小值时提升到 5 MiB
+        assert_eq!(options.effective_part_size(100), MIN_PART_SIZE);
+
+        // 超大文件需要增大分片以满足 10000 分片上限
+        let huge = 100 * 1024 * 1024 * 1024u64;
+        let size = options.effective_part_size(huge);
+        assert!(huge.div_ceil(size as u64) <= MAX_PARTS);
+    }
+
+    #[test]
+    fn test_options_from_config() {
+        let mut config = HashMap::new();
+        config.insert("multipart_part_size".to_string(), "8388608".to_string());
+        config.insert("multipart_concurrency".to_string(), "8".to_string());
+
+        let options = MultipartOptions::from_config(&config).unwrap();
+        assert_eq!(options.part_size, 8 * 1024 * 1024);
+        assert_eq!(options.concurrency, 8);
+
+        config.insert("multipart_concurrency".to_string(), "x".to_string());
+        assert!(MultipartOptions::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_session_roundtrip_and_list() {
+        let dir = TempDir::new().unwrap();
+        let session = MultipartSession {
+            bucket: "b".to_string(),
+            key: "big.tar".to_string(),
+            upload_id: "id-1".to_string(),
+            local_path: "/data/big.tar".to_string(),
+            file_size: 12 * 1024 * 1024,
+            file_modified: Some(1),
+            part_size: MIN_PART_SIZE,
+            completed_parts: vec![CompletedPart {
+                part_number: 1,
+                etag: "\"e1\"".to_string(),
+                size: MIN_PART_SIZE as u64,
+            }],
+        };
+
+        let path = MultipartSession::state_path(dir.path(), "b", "big.tar", "/data/big.tar");
+        session.save(&path).unwrap();
+
+        let loaded = MultipartSession::load(&path).unwrap();
+        assert_eq!(loaded.upload_id, "id-1");
+        assert_eq!(loaded.part_count(), 3);
+        assert!(loaded.matches_file(12 * 1024 * 1024, Some(1)));
+        assert!(!loaded.matches_file(12 * 1024 * 1024, Some(2)));
+
+        assert_eq!(MultipartSession::list(dir.path()).len(), 1);
+    }
+}
@@ -0,0 +1,423 @@
+use std::fmt::{Debug, Formatter};
+use std::io::SeekFrom;
+use std::sync::Arc;
+
+use chrono::DateTime;
+use log::{debug, warn};
+use opendal::raw::*;
+use opendal::{Buffer, EntryMode, Error, ErrorKind, Metadata, Result, Scheme};
+use russh_sftp::client::fs::File;
+use russh_sftp::protocol::{FileAttributes, OpenFlags};
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::Mutex;
+
+use super::session::{is_not_found, parse_sftp_error, SftpConnection};
+use super::SftpProtocol;
+
+/// 每次读取的最大字节数
+const READ_CHUNK_SIZE: usize = 2 * 1024 * 1024;
+
+/// 基于 russh 的 OpenDAL SFTP 后端
+///
+/// OpenDAL 自带的 sftp 服务依赖系统 ssh 命令且禁用了交互认证，
+/// 无法使用密码登录或指定 known_hosts 文件，也不支持 Windows，因此这里自行实现。
+#[derive(Clone)]
+pub struct SftpBackend {
+    core: Arc<SftpCore>,
+}
+
+struct SftpCore {
+    info: Arc<AccessorInfo>,
+    protocol: SftpProtocol,
+    /// 懒加载的连接，断开后下次使用时重建
+    conn: Mutex<Option<Arc<SftpConnection>>>,
+}
+
+impl SftpCore {
+    async fn connect(&self) -> Result<Arc<SftpConnection>> {
+        let mut conn = self.conn.lock().await;
+        if let Some(existing) = conn.as_ref().filter(|c| !c.is_closed()) {
+            return Ok(existing.clone());
+        }
+
+        let connected = Arc::new(SftpConnection::connect(&self.protocol).await?);
+        *conn = Some(connected.clone());
+        Ok(connected)
+    }
+}
+
+impl SftpBackend {
+    pub fn new(protocol: SftpProtocol) -> Self {
+        let info = AccessorInfo::default();
+        info.set_scheme(Scheme::Sftp)
+            .set_root(&normalize_root(
+                protocol.root.as_deref().unwrap_or_default(),
+            ))
+            .set_native_capability(opendal::Capability {
+                stat: true,
+                stat_has_content_length: true,
+                stat_has_last_modified: true,
+
+                read: true,
+
+                write: true,
+                write_can_multi: true,
+
+                create_dir: true,
+                delete: true,
+
+                list: true,
+                list_has_content_length: true,
+                list_has_last_modified: true,
+
+                rename: true,
+
+                shared: true,
+
+                ..Default::default()
+            });
+
+        Self {
+            core: Arc::new(SftpCore {
+                info: Arc::new(info),
+                protocol,
+                conn: Mutex::new(None),
+            }),
+        }
+    }
+}
+
+impl Debug for SftpBackend {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let protocol = &self.core.protocol;
+        f.debug_struct("SftpBackend")
+            .field("host", &protocol.host)
+            .field("port", &protocol.port)
+            .field("username", &protocol.username)
+            .field("root", &protocol.root)
+            .finish()
+    }
+}
+
+impl Access for SftpBackend {
+    type Reader = SftpReader;
+    type Writer = SftpWriter;
+    type Lister = SftpLister;
+    type Deleter = oio::OneShotDeleter<SftpDeleter>;
+    type BlockingReader = ();
+    type BlockingWriter = ();
+    type BlockingLister = ();
+    type BlockingDeleter = ();
+
+    fn info(&self) -> Arc<AccessorInfo> {
+        self.core.info.clone()
+    }
+
+    async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        let conn = self.core.connect().await?;
+        conn.create_dir_all(&conn.abs_path(path)).await?;
+        Ok(RpCreateDir::default())
+    }
+
+    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+        let conn = self.core.connect().await?;
+        let attrs = conn
+            .sftp()
+            .metadata(conn.abs_path(path))
+            .await
+            .map_err(parse_sftp_error)?;
+
+        let meta = to_metadata(&attrs);
+        if path.ends_with('/') && !meta.is_dir() {
+            return Err(Error::new(ErrorKind::NotFound, "路径不是目录"));
+        }
+        Ok(RpStat::new(meta))
+    }
+
+    async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let conn = self.core.connect().await?;
+        let mut file = conn
+            .sftp()
+            .open(conn.abs_path(path))
+            .await
+            .map_err(parse_sftp_error)?;
+
+        let offset = args.range().offset();
+        if offset != 0 {
+            file.seek(SeekFrom::Start(offset))
+                .await
+                .map_err(new_std_io_error)?;
+        }
+
+        Ok((
+            RpRead::default(),
+            SftpReader {
+                _conn: conn,
+                file: Mutex::new(file),
+                remaining: args.range().size(),
+            },
+        ))
+    }
+
+    async fn write(&self, path: &str, op: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let conn = self.core.connect().await?;
+        let abs_path = conn.abs_path(path);
+        if let Some((parent, _)) = abs_path.rsplit_once('/') {
+            conn.create_dir_all(parent).await?;
+        }
+
+        let flags = if op.append() {
+            OpenFlags::CREATE | OpenFlags::WRITE | OpenFlags::APPEND
+        } else {
+            OpenFlags::CREATE | OpenFlags::WRITE | OpenFlags::TRUNCATE
+        };
+        let file = conn
+            .sftp()
+            .open_with_flags(abs_path, flags)
+            .await
+            .map_err(parse_sftp_error)?;
+
+        Ok((
+            RpWrite::default(),
+            SftpWriter {
+                _conn: conn,
+                file: Mutex::new(file),
+            },
+        ))
+    }
+
+    async fn delete(&self) -> Result<(RpDelete, Self::Deleter)> {
+        Ok((
+            RpDelete::default(),
+            oio::OneShotDeleter::new(SftpDeleter {
+                core: self.core.clone(),
+            }),
+        ))
+    }
+
+    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
+        let conn = self.core.connect().await?;
+        let entries = match conn.sftp().read_dir(conn.abs_path(path)).await {
+            Ok(dir) => dir,
+            Err(e) if is_not_found(&e) => return Ok((RpList::default(), SftpLister::empty())),
+            Err(e) => return Err(parse_sftp_error(e)),
+        };
+
+        let prefix = if path == "/" { "" } else { path };
+        // list 需要先返回目录自身
+        let mut items = vec![oio::Entry::new(path, Metadata::new(EntryMode::DIR))];
+        for entry in entries {
+            let meta = to_metadata(&entry.metadata());
+            let name = if meta.is_dir() {
+                format!("{}{}/", prefix, entry.file_name())
+            } else {
+                format!("{}{}", prefix, entry.file_name())
+            };
+            items.push(oio::Entry::new(&name, meta));
+        }
+
+        Ok((
+            RpList::default(),
+            SftpLister {
+                items: items.into_iter(),
+            },
+        ))
+    }
+
+    async fn rename(&self, from: &str, to: &str, _: OpRename) -> Result<RpRename> {
+        let conn = self.core.connect().await?;
+        let sftp = conn.sftp();
+        let (from, to) = (conn.abs_path(from), conn.abs_path(to));
+        if let Some((parent, _)) = to.rsplit_once('/') {
+            conn.create_dir_all(parent).await?;
+        }
+
+        if conn.supports_posix_rename() {
+            conn.posix_rename(&from, &to).await?;
+            return Ok(RpRename::default());
+        }
+
+        // SFTP v3 的 rename 不覆盖已存在的文件：先把目标移到临时名称，
+        // 重命名失败时再移回来，避免丢失原有的目标文件
+        let backup = match sftp.try_exists(to.as_str()).await {
+            Ok(true) => {
+                let backup = format!("{}.mpfm-rename-{}", to, uuid::Uuid::new_v4().simple());
+                sftp.rename(to.as_str(), backup.as_str())
+                    .await
+                    .map_err(parse_sftp_error)?;
+                debug!("重命名前暂存已存在的目标: {} -> {}", to, backup);
+                Some(backup)
+            }
+            Ok(false) => None,
+            Err(e) => return Err(parse_sftp_error(e)),
+        };
+
+        if let Err(e) = sftp.rename(from.as_str(), to.as_str()).await {
+            if let Some(backup) = &backup {
+                if let Err(restore) = sftp.rename(backup.as_str(), to.as_str()).await {
+                    warn!("恢复重命名目标失败，原文件保留在 {}: {}", backup, restore);
+                }
+            }
+            return Err(parse_sftp_error(e));
+        }
+
+        if let Some(backup) = backup {
+            match sftp.remove_file(backup.as_str()).await {
+                Ok(()) => {}
+                Err(e) if is_not_found(&e) => {}
+                Err(e) => warn!("删除暂存的重命名目标失败: {}: {}", backup, e),
+            }
+        }
+
+        Ok(RpRename::default())
+    }
+}
+
+fn to_metadata(attrs: &FileAttributes) -> Metadata {
+    let mode = if attrs.is_dir() {
+        EntryMode::DIR
+    } else if attrs.is_regular() {
+        EntryMode::FILE
+    } else {
+        EntryMode::Unknown
+    };
+
+    let mut meta = Metadata::new(mode);
+    if let Some(size) = attrs.size {
+        meta.set_content_length(size);
+    }
+    if let Some(modified) = attrs
+        .mtime
+        .and_then(|mtime| DateTime::from_timestamp(mtime as i64, 0))
+    {
+        meta.set_last_modified(modified);
+    }
+    meta
+}
+
+/// 文件句柄只在 `&mut self` 中使用，Mutex 仅用于满足 OpenDAL 对 Sync 的要求
+pub struct SftpReader {
+    /// 读取期间保持连接
+    _conn: Arc<SftpConnection>,
+    file: Mutex<File>,
+    remaining: Option<u64>,
+}
+
+impl oio::Read for SftpReader {
+    async fn read(&mut self) -> Result<Buffer> {
+        let size = match self.remaining {
+            Some(0) => return Ok(Buffer::new()),
+            Some(remaining) => (remaining as usize).min(READ_CHUNK_SIZE),
+            None => READ_CHUNK_SIZE,
+        };
+
+        let mut buf = vec![0; size];
+        let n = self
+            .file
+            .get_mut()
+            .read(&mut buf)
+            .await
+            .map_err(new_std_io_error)?;
+        buf.truncate(n);
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining -= n as u64;
+        }
+        Ok(Buffer::from(buf))
+    }
+}
+
+pub struct SftpWriter {
+    _conn: Arc<SftpConnection>,
+    file: Mutex<File>,
+}
+
+impl oio::Write for SftpWriter {
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let file = self.file.get_mut();
+        for chunk in bs {
+            file.write_all(&chunk).await.map_err(new_std_io_error)?;
+        }
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<Metadata> {
+        self.file
+            .get_mut()
+            .shutdown()
+            .await
+            .map_err(new_std_io_error)?;
+        Ok(Metadata::default())
+    }
+
+    async fn abort(&mut self) -> Result<()> {
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "SftpWriter doesn't support abort",
+        ))
+    }
+}
+
+/// SFTP 一次性读出整个目录，这里按条返回
+pub struct SftpLister {
+    items: std::vec::IntoIter<oio::Entry>,
+}
+
+impl SftpLister {
+    fn empty() -> Self {
+        Self {
+            items: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl oio::List for SftpLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        Ok(self.items.next())
+    }
+}
+
+pub struct SftpDeleter {
+    core: Arc<SftpCore>,
+}
+
+impl oio::OneShotDelete for SftpDeleter {
+    async fn delete_once(&self, path: String, _: OpDelete) -> Result<()> {
+        let conn = self.core.connect().await?;
+        let abs_path = conn.abs_path(&path);
+
+        let result = if path.ends_with('/') {
+            conn.sftp().remove_dir(abs_path).await
+        } else {
+            conn.sftp().remove_file(abs_path).await
+        };
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) if is_not_found(&e) => Ok(()),
+            Err(e) => Err(parse_sftp_error(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_metadata() {
+        let mut attrs = FileAttributes::empty();
+        attrs.set_regular(true);
+        attrs.size = Some(42);
+        attrs.mtime = Some(1_700_000_000);
+
+        let meta = to_metadata(&attrs);
+        assert!(meta.is_file());
+        assert_eq!(meta.content_length(), 42);
+        assert_eq!(meta.last_modified().unwrap().timestamp(), 1_700_000_000);
+
+        let mut attrs = FileAttributes::empty();
+        attrs.set_dir(true);
+        assert!(to_metadata(&attrs).is_dir());
+    }
+}
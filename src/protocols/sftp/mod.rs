@@ -0,0 +1,354 @@
+pub mod backend;
+pub mod session;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use log::debug;
+use opendal::{Operator, OperatorBuilder};
+
+use super::limits::{apply_layers, ConnectionLimits};
+use super::retry::RetryPolicy;
+use super::traits::{Capabilities, Protocol};
+use crate::core::error::{Error, Result};
+use backend::SftpBackend;
+
+/// 连接配置中认证与主机校验相关的键
+pub const PRIVATE_KEY_KEY: &str = "private_key";
+pub const PASSPHRASE_KEY: &str = "passphrase";
+pub const KNOWN_HOSTS_KEY: &str = "known_hosts";
+pub const KNOWN_HOSTS_STRATEGY_KEY: &str = "known_hosts_strategy";
+
+/// SFTP 登录方式
+///
+/// 同时配置私钥与密码时先尝试私钥，服务器拒绝后再用密码。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SftpAuth {
+    pub password: Option<String>,
+    /// 私钥文件路径（OpenSSH、PKCS#8 等格式）
+    pub private_key: Option<PathBuf>,
+    /// 加密私钥的口令
+    pub passphrase: Option<String>,
+}
+
+impl SftpAuth {
+    pub fn password(password: String) -> Self {
+        Self {
+            password: Some(password),
+            private_key: None,
+            passphrase: None,
+        }
+    }
+
+    pub fn private_key(path: PathBuf, passphrase: Option<String>) -> Self {
+        Self {
+            password: None,
+            private_key: Some(path),
+            passphrase,
+        }
+    }
+
+    fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let non_empty = |key: &str| config.get(key).filter(|v| !v.is_empty()).cloned();
+
+        let auth = Self {
+            password: non_empty("password"),
+            private_key: non_empty(PRIVATE_KEY_KEY).map(PathBuf::from),
+            passphrase: non_empty(PASSPHRASE_KEY),
+        };
+        if auth.password.is_none() && auth.private_key.is_none() {
+            return Err(Error::new_config(
+                "SFTP配置缺少 'password' 或 'private_key' 参数",
+            ));
+        }
+        Ok(auth)
+    }
+}
+
+/// 服务器主机密钥的校验策略，语义与 OpenSSH 的 StrictHostKeyChecking 一致
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownHostsStrategy {
+    /// 只接受 known_hosts 中已记录的主机密钥
+    Strict,
+    /// 未记录的主机自动写入 known_hosts，已记录但密钥变化时拒绝
+    Add,
+    /// 不校验主机密钥，仅用于测试环境
+    Accept,
+}
+
+impl KnownHostsStrategy {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "strict" => Ok(Self::Strict),
+            "add" => Ok(Self::Add),
+            "accept" => Ok(Self::Accept),
+            other => Err(Error::new_config(&format!(
+                "无效的 known_hosts 策略: {}（可选 strict、add、accept）",
+                other
+            ))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::Add => "add",
+            Self::Accept => "accept",
+        }
+    }
+}
+
+/// 主机密钥校验配置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownHosts {
+    pub strategy: KnownHostsStrategy,
+    /// known_hosts 文件路径，默认 `~/.ssh/known_hosts`
+    pub path: Option<PathBuf>,
+}
+
+impl Default for KnownHosts {
+    fn default() -> Self {
+        Self {
+            strategy: KnownHostsStrategy::Strict,
+            path: None,
+        }
+    }
+}
+
+impl KnownHosts {
+    fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        Ok(Self {
+            strategy: KnownHostsStrategy::parse(
+                config
+                    .get(KNOWN_HOSTS_STRATEGY_KEY)
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+            )?,
+            path: config
+                .get(KNOWN_HOSTS_KEY)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SftpProtocol {
+    host: String,
+    port: u16,
+    username: String,
+    auth: SftpAuth,
+    known_hosts: KnownHosts,
+    /// 根目录；相对路径基于登录用户的主目录，未设置时即为主目录
+    root: Option<String>,
+    retry: RetryPolicy,
+    limits: ConnectionLimits,
+}
+
+impl SftpProtocol {
+    pub fn new(
+        host: String,
+        port: u16,
+        username: String,
+        auth: SftpAuth,
+        root: Option<String>,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            username,
+            auth,
+            known_hosts: KnownHosts::default(),
+            root,
+            retry: RetryPolicy::default(),
+            limits: ConnectionLimits::default(),
+        }
+    }
+
+    /// 设置主机密钥校验方式
+    pub fn with_known_hosts(mut self, known_hosts: KnownHosts) -> Self {
+        self.known_hosts = known_hosts;
+        self
+    }
+
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置超时与并发限制
+    pub fn with_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let host = config
+            .get("host")
+            .ok_or_else(|| Error::new_config("SFTP配置缺少 'host' 参数"))?
+            .clone();
+
+        let port = config
+            .get("port")
+            .map(|p| p.parse::<u16>())
+            .unwrap_or(Ok(22))
+            .map_err(|_| Error::new_config("SFTP端口配置无效"))?;
+
+        let username = config
+            .get("username")
+            .ok_or_else(|| Error::new_config("SFTP配置缺少 'username' 参数"))?
+            .clone();
+
+        let root = config
+            .get("root_dir")
+            .or_else(|| config.get("root"))
+            .filter(|r| !r.is_empty())
+            .cloned();
+
+        Ok(
+            Self::new(host, port, username, SftpAuth::from_config(config)?, root)
+                .with_known_hosts(KnownHosts::from_config(config)?)
+                .with_retry(RetryPolicy::from_config(config)?)
+                .with_limits(ConnectionLimits::from_config(config)?),
+        )
+    }
+}
+
+impl Protocol for SftpProtocol {
+    fn create_operator(&self) -> Result<Operator> {
+        debug!(
+            "创建 SFTP 操作符, host: {}, port: {}, username: {}, known_hosts: {}",
+            self.host,
+            self.port,
+            self.username,
+            self.known_hosts.strategy.as_str()
+        );
+
+        // SSH 连接在第一次操作时才建立
+        let op = OperatorBuilder::new(SftpBackend::new(self.clone())).finish();
+
+        Ok(apply_layers(op, &self.retry, &self.limits, "sftp"))
+    }
+
+    fn get_id(&self) -> String {
+        format!("sftp://{}@{}:{}", self.username, self.host, self.port)
+    }
+
+    fn get_name(&self) -> String {
+        format!("SFTP ({}@{}:{})", self.username, self.host, self.port)
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::default()
+            .with_list(true)
+            .with_read(true)
+            .with_write(true)
+            .with_create_dir(true)
+            .with_delete(true)
+            .with_copy(false)
+            .with_rename(true)
+            .with_batch_delete(false)
+    }
+
+    fn get_rate_limit(&self) -> Option<u64> {
+        self.limits.rate_limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("host".to_string(), "127.0.0.1".to_string());
+        config.insert("port".to_string(), "2222".to_string());
+        config.insert("username".to_string(), "testuser".to_string());
+        config
+    }
+
+    #[test]
+    fn test_sftp_protocol_from_config_password() {
+        let mut config = base_config();
+        config.insert("password".to_string(), "testpass".to_string());
+        config.insert("root".to_string(), "upload".to_string());
+
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        assert_eq!(protocol.host, "127.0.0.1");
+        assert_eq!(protocol.port, 2222);
+        assert_eq!(protocol.username, "testuser");
+        assert_eq!(protocol.auth, SftpAuth::password("testpass".to_string()));
+        assert_eq!(protocol.root, Some("upload".to_string()));
+        assert_eq!(protocol.known_hosts, KnownHosts::default());
+    }
+
+    #[test]
+    fn test_sftp_protocol_from_config_private_key() {
+        let mut config = base_config();
+        config.remove("port");
+        config.insert(
+            PRIVATE_KEY_KEY.to_string(),
+            "/home/me/.ssh/id_ed25519".to_string(),
+        );
+        config.insert(PASSPHRASE_KEY.to_string(), "secret".to_string());
+        config.insert(KNOWN_HOSTS_KEY.to_string(), "/tmp/known_hosts".to_string());
+        config.insert(KNOWN_HOSTS_STRATEGY_KEY.to_string(), "add".to_string());
+
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        assert_eq!(protocol.port, 22);
+        assert_eq!(
+            protocol.auth,
+            SftpAuth::private_key(
+                PathBuf::from("/home/me/.ssh/id_ed25519"),
+                Some("secret".to_string())
+            )
+        );
+        assert_eq!(protocol.known_hosts.strategy, KnownHostsStrategy::Add);
+        assert_eq!(
+            protocol.known_hosts.path,
+            Some(PathBuf::from("/tmp/known_hosts"))
+        );
+        assert_eq!(protocol.root, None);
+    }
+
+    #[test]
+    fn test_sftp_protocol_requires_credentials() {
+        let mut config = base_config();
+        assert!(SftpProtocol::from_config(&config).is_err());
+
+        config.insert("password".to_string(), String::new());
+        assert!(SftpProtocol::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_known_hosts_strategy_parse() {
+        assert_eq!(
+            KnownHostsStrategy::parse("").unwrap(),
+            KnownHostsStrategy::Strict
+        );
+        assert_eq!(
+            KnownHostsStrategy::parse("Accept").unwrap(),
+            KnownHostsStrategy::Accept
+        );
+        assert!(KnownHostsStrategy::parse("yes").is_err());
+    }
+
+    #[test]
+    fn test_sftp_protocol_id_and_name() {
+        let protocol = SftpProtocol::new(
+            "example.com".to_string(),
+            22,
+            "user".to_string(),
+            SftpAuth::password("pass".to_string()),
+            None,
+        );
+        assert_eq!(protocol.get_id(), "sftp://user@example.com:22");
+        assert_eq!(protocol.get_name(), "SFTP (user@example.com:22)");
+
+        let caps = protocol.get_capabilities();
+        assert!(caps.can_rename);
+        assert!(!caps.can_copy);
+        assert!(!caps.can_batch_delete);
+    }
+}
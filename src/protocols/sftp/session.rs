@@ -0,0 +1,457 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::{debug, warn};
+use opendal::raw::{build_rooted_abs_path, normalize_root};
+use opendal::{Error, ErrorKind, Result};
+use russh::client::{self, Handle};
+use russh::keys::{known_hosts, PrivateKeyWithHashAlg, PublicKey, PublicKeyOrCertificate};
+use russh_sftp::client::error::Error as SftpError;
+use russh_sftp::client::{RawSftpSession, SftpSession};
+use russh_sftp::protocol::{Packet, StatusCode};
+use serde::Serialize;
+
+use super::{KnownHosts, KnownHostsStrategy, SftpProtocol};
+
+/// OpenSSH 的原子重命名扩展，目标存在时直接覆盖
+const POSIX_RENAME: &str = "posix-rename@openssh.com";
+
+#[derive(Serialize)]
+struct PosixRenameExtension {
+    oldpath: String,
+    newpath: String,
+}
+
+/// 一条已认证的 SSH 连接及其上的 SFTP 会话
+///
+/// SFTP 请求可以在同一会话上并发进行，因此一个操作符只需要一条连接。
+pub struct SftpConnection {
+    handle: Handle<HostKeyVerifier>,
+    sftp: SftpSession,
+    /// 服务端支持 `posix-rename@openssh.com` 时保留的原始会话，用于发送扩展请求
+    posix_rename: Option<RawSftpSession>,
+    /// 远端根目录的绝对路径，形如 `/home/user/data/`
+    root: String,
+}
+
+impl SftpConnection {
+    pub async fn connect(protocol: &SftpProtocol) -> Result<Self> {
+        debug!(
+            "建立 SSH 连接: {}@{}:{}",
+            protocol.username, protocol.host, protocol.port
+        );
+
+        let config = Arc::new(client::Config {
+            keepalive_interval: Some(Duration::from_secs(30)),
+            ..Default::default()
+        });
+        let verifier = HostKeyVerifier::new(&protocol.host, protocol.port, &protocol.known_hosts);
+        let mut handle = client::connect(config, (protocol.host.as_str(), protocol.port), verifier)
+            .await
+            .map_err(|e| match e {
+                VerifyError::Rejected(message) => Error::new(ErrorKind::PermissionDenied, message),
+                VerifyError::Ssh(e) => parse_ssh_error(e),
+            })?;
+
+        authenticate(&mut handle, protocol).await?;
+
+        let channel = handle
+            .channel_open_session()
+            .await
+            .map_err(parse_ssh_error)?;
+        channel
+            .request_subsystem(true, "sftp")
+            .await
+            .map_err(parse_ssh_error)?;
+        let sftp = SftpSession::new(channel.into_stream())
+            .await
+            .map_err(parse_sftp_error)?;
+
+        let root = resolve_root(&sftp, protocol.root.as_deref()).await?;
+        let posix_rename = open_posix_rename_session(&handle).await;
+        debug!(
+            "SFTP 会话已建立，根目录: {}, 支持 posix-rename: {}",
+            root,
+            posix_rename.is_some()
+        );
+
+        Ok(Self {
+            handle,
+            sftp,
+            posix_rename,
+            root,
+        })
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.handle.is_closed()
+    }
+
+    pub fn sftp(&self) -> &SftpSession {
+        &self.sftp
+    }
+
+    pub fn supports_posix_rename(&self) -> bool {
+        self.posix_rename.is_some()
+    }
+
+    /// 使用 `posix-rename@openssh.com` 扩展重命名，目标已存在时原子地覆盖
+    pub async fn posix_rename(&self, from: &str, to: &str) -> Result<()> {
+        let raw = self
+            .posix_rename
+            .as_ref()
+            .ok_or_else(|| Error::new(ErrorKind::Unsupported, "服务端不支持 posix-rename 扩展"))?;
+        let data = russh_sftp::ser::to_bytes(&PosixRenameExtension {
+            oldpath: from.to_string(),
+            newpath: to.to_string(),
+        })
+        .map_err(|e| {
+            Error::new(ErrorKind::Unexpected, "编码 posix-rename 请求失败").set_source(e)
+        })?;
+
+        match raw
+            .extended(POSIX_RENAME, data.to_vec())
+            .await
+            .map_err(parse_sftp_error)?
+        {
+            Packet::Status(status) if status.status_code == StatusCode::Ok => Ok(()),
+            Packet::Status(status) => Err(parse_sftp_error(SftpError::Status(status))),
+            _ => Err(parse_sftp_error(SftpError::UnexpectedPacket)),
+        }
+    }
+
+    /// 把操作符中的相对路径转换为远端绝对路径（目录不带末尾的 '/'）
+    pub fn abs_path(&self, path: &str) -> String {
+        let abs = build_rooted_abs_path(&self.root, path);
+        match abs.trim_end_matches('/') {
+            "" => "/".to_string(),
+            trimmed => trimmed.to_string(),
+        }
+    }
+
+    /// 逐级创建目录，已存在的目录跳过
+    pub async fn create_dir_all(&self, abs_path: &str) -> Result<()> {
+        let mut current = String::new();
+        for part in abs_path.split('/').filter(|p| !p.is_empty()) {
+            current.push('/');
+            current.push_str(part);
+            if !self.exists(&current).await? {
+                if let Err(e) = self.sftp.create_dir(current.as_str()).await {
+                    // 并发创建同一目录时，对方可能已经建好
+                    if !self.exists(&current).await? {
+                        return Err(parse_sftp_error(e));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn exists(&self, abs_path: &str) -> Result<bool> {
+        self.sftp
+            .try_exists(abs_path)
+            .await
+            .map_err(parse_sftp_error)
+    }
+}
+
+/// 服务端声明支持 `posix-rename@openssh.com` 时，在单独的通道上打开一个原始会话
+///
+/// `SftpSession` 不公开服务端声明的扩展，也无法发送任意扩展请求，失败时退回普通重命名。
+async fn open_posix_rename_session(handle: &Handle<HostKeyVerifier>) -> Option<RawSftpSession> {
+    let channel = handle.channel_open_session().await.ok()?;
+    channel.request_subsystem(true, "sftp").await.ok()?;
+    let raw = RawSftpSession::new(channel.into_stream());
+    match raw.init().await {
+        Ok(version)
+            if version
+                .extensions
+                .get(POSIX_RENAME)
+                .is_some_and(|v| v == "1") =>
+        {
+            Some(raw)
+        }
+        Ok(_) => None,
+        Err(e) => {
+            debug!("探测 posix-rename 扩展失败: {}", e);
+            None
+        }
+    }
+}
+
+/// 先尝试私钥，再尝试密码
+async fn authenticate(handle: &mut Handle<HostKeyVerifier>, protocol: &SftpProtocol) -> Result<()> {
+    let auth = &protocol.auth;
+
+    if let Some(path) = &auth.private_key {
+        let key = russh::keys::load_secret_key(path, auth.passphrase.as_deref()).map_err(|e| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                format!("无法加载私钥 {}", path.display()),
+            )
+            .set_source(e)
+        })?;
+        let hash_alg = handle
+            .best_supported_rsa_hash()
+            .await
+            .map_err(parse_ssh_error)?
+            .flatten();
+        let result = handle
+            .authenticate_publickey(
+                protocol.username.as_str(),
+                PrivateKeyWithHashAlg::new(Arc::new(key), hash_alg),
+            )
+            .await
+            .map_err(parse_ssh_error)?;
+        if result.success() {
+            return Ok(());
+        }
+        debug!("私钥认证被拒绝: {}", protocol.username);
+    }
+
+    if let Some(password) = &auth.password {
+        let result = handle
+            .authenticate_password(protocol.username.as_str(), password.as_str())
+            .await
+            .map_err(parse_ssh_error)?;
+        if result.success() {
+            return Ok(());
+        }
+    }
+
+    Err(Error::new(
+        ErrorKind::PermissionDenied,
+        format!("SFTP 认证失败: {}", protocol.username),
+    ))
+}
+
+/// 计算根目录的绝对路径并确保其存在
+async fn resolve_root(sftp: &SftpSession, root: Option<&str>) -> Result<String> {
+    let base = match root {
+        Some(root) if root.starts_with('/') => root.to_string(),
+        root => {
+            let home = sftp.canonicalize(".").await.map_err(parse_sftp_error)?;
+            match root {
+                Some(root) => format!("{}/{}", home, root),
+                None => home,
+            }
+        }
+    };
+    let root = normalize_root(&base);
+
+    let mut current = String::new();
+    for part in root.split('/').filter(|p| !p.is_empty()) {
+        current.push('/');
+        current.push_str(part);
+        let exists = sftp
+            .try_exists(current.as_str())
+            .await
+            .map_err(parse_sftp_error)?;
+        if !exists {
+            sftp.create_dir(current.as_str())
+                .await
+                .map_err(parse_sftp_error)?;
+        }
+    }
+
+    Ok(root)
+}
+
+/// 握手阶段的错误：主机密钥被拒绝时带上具体原因
+#[derive(Debug)]
+pub enum VerifyError {
+    Rejected(String),
+    Ssh(russh::Error),
+}
+
+impl From<russh::Error> for VerifyError {
+    fn from(e: russh::Error) -> Self {
+        Self::Ssh(e)
+    }
+}
+
+/// 按 known_hosts 校验服务器主机密钥
+pub struct HostKeyVerifier {
+    host: String,
+    port: u16,
+    strategy: KnownHostsStrategy,
+    path: Option<PathBuf>,
+}
+
+impl HostKeyVerifier {
+    fn new(host: &str, port: u16, known_hosts: &KnownHosts) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            strategy: known_hosts.strategy,
+            path: known_hosts.path.clone(),
+        }
+    }
+
+    fn known_hosts_path(&self) -> std::result::Result<PathBuf, VerifyError> {
+        self.path
+            .clone()
+            .or_else(|| dirs::home_dir().map(|home| home.join(".ssh").join("known_hosts")))
+            .ok_or_else(|| VerifyError::Rejected("无法确定 known_hosts 文件位置".to_string()))
+    }
+
+    fn verify(&self, key: &PublicKey) -> std::result::Result<bool, VerifyError> {
+        if self.strategy == KnownHostsStrategy::Accept {
+            warn!("未校验 {}:{} 的主机密钥", self.host, self.port);
+            return Ok(true);
+        }
+
+        let path = self.known_hosts_path()?;
+        match known_hosts::check_known_hosts_path(&self.host, self.port, key, &path) {
+            Ok(true) => Ok(true),
+            Ok(false) if self.strategy == KnownHostsStrategy::Add => {
+                known_hosts::learn_known_hosts_path(&self.host, self.port, key, &path).map_err(
+                    |e| {
+                        VerifyError::Rejected(format!(
+                            "无法写入 known_hosts {}: {}",
+                            path.display(),
+                            e
+                        ))
+                    },
+                )?;
+                debug!(
+                    "已将 {}:{} 的主机密钥写入 {}",
+                    self.host,
+                    self.port,
+                    path.display()
+                );
+                Ok(true)
+            }
+            Ok(false) => Err(VerifyError::Rejected(format!(
+                "{} 中没有 {}:{} 的主机密钥",
+                path.display(),
+                self.host,
+                self.port
+            ))),
+            Err(russh::keys::Error::KeyChanged { line }) => Err(VerifyError::Rejected(format!(
+                "{}:{} 的主机密钥与 {} 第 {} 行记录的不一致",
+                self.host,
+                self.port,
+                path.display(),
+                line
+            ))),
+            Err(e) => Err(VerifyError::Rejected(format!(
+                "读取 known_hosts {} 失败: {}",
+                path.display(),
+                e
+            ))),
+        }
+    }
+}
+
+impl client::Handler for HostKeyVerifier {
+    type Error = VerifyError;
+
+    async fn check_server_key(
+        &mut self,
+        server_public_key: &PublicKeyOrCertificate,
+    ) -> std::result::Result<bool, Self::Error> {
+        match server_public_key {
+            PublicKeyOrCertificate::PublicKey { key, .. } => self.verify(key),
+            PublicKeyOrCertificate::Certificate(cert) => {
+                self.verify(&PublicKey::from(cert.public_key().clone()))
+            }
+        }
+    }
+}
+
+/// 转换 SSH 错误；只有连接中断、通道与 IO 错误、超时可以重试，认证与协议错误直接失败
+pub fn parse_ssh_error(e: russh::Error) -> Error {
+    let temporary = matches!(
+        e,
+        russh::Error::Disconnect
+            | russh::Error::HUP
+            | russh::Error::IO(_)
+            | russh::Error::ConnectionTimeout
+            | russh::Error::KeepaliveTimeout
+            | russh::Error::InactivityTimeout
+            | russh::Error::Elapsed(_)
+            | russh::Error::ChannelOpenFailure(_)
+            | russh::Error::WrongChannel
+            | russh::Error::SendError
+            | russh::Error::RecvError
+    );
+    let err = Error::new(ErrorKind::Unexpected, "ssh error").set_source(e);
+    if temporary {
+        err.set_temporary()
+    } else {
+        err
+    }
+}
+
+/// 转换 SFTP 错误；SSH_FX_FAILURE（磁盘已满、目录非空等）是永久错误，只有连接中断与超时可以重试
+pub fn parse_sftp_error(e: SftpError) -> Error {
+    let kind = match &e {
+        SftpError::Status(status) => match status.status_code {
+            StatusCode::NoSuchFile => ErrorKind::NotFound,
+            StatusCode::PermissionDenied => ErrorKind::PermissionDenied,
+            StatusCode::OpUnsupported => ErrorKind::Unsupported,
+            _ => ErrorKind::Unexpected,
+        },
+        _ => ErrorKind::Unexpected,
+    };
+    let temporary = match &e {
+        SftpError::Status(status) => matches!(
+            status.status_code,
+            StatusCode::NoConnection | StatusCode::ConnectionLost
+        ),
+        SftpError::IO(_) | SftpError::Timeout => true,
+        _ => false,
+    };
+
+    let err = Error::new(kind, "sftp error").set_source(e);
+    if temporary {
+        err.set_temporary()
+    } else {
+        err
+    }
+}
+
+pub fn is_not_found(e: &SftpError) -> bool {
+    matches!(e, SftpError::Status(status) if status.status_code == StatusCode::NoSuchFile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use russh_sftp::protocol::Status;
+
+    fn status(status_code: StatusCode) -> SftpError {
+        SftpError::Status(Status {
+            id: 1,
+            status_code,
+            error_message: String::new(),
+            language_tag: "en-US".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_parse_sftp_error_only_retries_connection_errors() {
+        assert!(!parse_sftp_error(status(StatusCode::Failure)).is_temporary());
+        assert!(!parse_sftp_error(status(StatusCode::BadMessage)).is_temporary());
+        assert_eq!(
+            parse_sftp_error(status(StatusCode::NoSuchFile)).kind(),
+            ErrorKind::NotFound
+        );
+
+        assert!(parse_sftp_error(status(StatusCode::ConnectionLost)).is_temporary());
+        assert!(parse_sftp_error(SftpError::Timeout).is_temporary());
+        assert!(parse_sftp_error(SftpError::IO("broken pipe".to_string())).is_temporary());
+    }
+
+    #[test]
+    fn test_parse_ssh_error_only_retries_connection_errors() {
+        assert!(!parse_ssh_error(russh::Error::NotAuthenticated).is_temporary());
+        assert!(!parse_ssh_error(russh::Error::UnknownKey).is_temporary());
+        assert!(!parse_ssh_error(russh::Error::Version).is_temporary());
+
+        assert!(parse_ssh_error(russh::Error::Disconnect).is_temporary());
+        assert!(parse_ssh_error(russh::Error::KeepaliveTimeout).is_temporary());
+    }
+}
@@ -74,4 +74,9 @@ pub trait Protocol: Debug + Send + Sync {
 
     /// 获取该协议的能力描述
     fn get_capabilities(&self) -> Capabilities;
+
+    /// 获取连接配置的传输带宽限制（字节/秒），未配置时不限速
+    fn get_rate_limit(&self) -> Option<u64> {
+        None
+    }
 }
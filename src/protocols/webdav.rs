@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use log::debug;
+use opendal::services::WebdavConfig;
+use opendal::Operator;
+
+use super::limits::{apply_layers, ConnectionLimits};
+use super::retry::RetryPolicy;
+use super::traits::{Capabilities, Protocol};
+use crate::core::error::{Error, Result};
+
+/// 连接配置中认证与兼容性相关的键
+pub const TOKEN_KEY: &str = "token";
+pub const DISABLE_COPY_KEY: &str = "disable_copy";
+
+/// WebDAV 登录方式
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebdavAuth {
+    /// 不携带认证信息，用于公开共享
+    Anonymous,
+    /// HTTP Basic 认证（Nextcloud/ownCloud 建议使用应用密码）
+    Basic { username: String, password: String },
+    /// Bearer Token 认证
+    Bearer(String),
+}
+
+impl WebdavAuth {
+    fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let non_empty = |key: &str| config.get(key).filter(|v| !v.is_empty()).cloned();
+
+        match (non_empty("username"), non_empty(TOKEN_KEY)) {
+            (Some(_), Some(_)) => Err(Error::new_config(
+                "WebDAV配置不能同时指定 'username' 和 'token'",
+            )),
+            (Some(username), None) => Ok(Self::Basic {
+                username,
+                password: non_empty("password").unwrap_or_default(),
+            }),
+            (None, Some(token)) => Ok(Self::Bearer(token)),
+            (None, None) => Ok(Self::Anonymous),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct WebdavProtocol {
+    /// 服务地址，例如 `https://cloud.example.com/remote.php/dav/files/alice`
+    endpoint: String,
+    auth: WebdavAuth,
+    root: Option<String>,
+    /// 服务器不支持 COPY 方法时关闭服务端复制
+    disable_copy: bool,
+    retry: RetryPolicy,
+    limits: ConnectionLimits,
+}
+
+impl WebdavProtocol {
+    pub fn new(endpoint: String, auth: WebdavAuth, root: Option<String>) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            auth,
+            root,
+            disable_copy: false,
+            retry: RetryPolicy::default(),
+            limits: ConnectionLimits::default(),
+        }
+    }
+
+    /// 关闭服务端复制，复制时改为读写方式
+    pub fn with_disable_copy(mut self, disable_copy: bool) -> Self {
+        self.disable_copy = disable_copy;
+        self
+    }
+
+    /// 设置重试策略
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// 设置超时与并发限制
+    pub fn with_limits(mut self, limits: ConnectionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    pub fn from_config(config: &HashMap<String, String>) -> Result<Self> {
+        let endpoint = config
+            .get("endpoint")
+            .filter(|e| !e.is_empty())
+            .ok_or_else(|| Error::new_config("WebDAV配置缺少 'endpoint' 参数"))?
+            .clone();
+        if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+            return Err(Error::new_config(&format!(
+                "WebDAV端点必须以 http:// 或 https:// 开头: {}",
+                endpoint
+            )));
+        }
+
+        let root = config
+            .get("root_dir")
+            .or_else(|| config.get("root"))
+            .filter(|r| !r.is_empty())
+            .cloned();
+
+        let disable_copy = config
+            .get(DISABLE_COPY_KEY)
+            .map(|s| s.parse::<bool>())
+            .unwrap_or(Ok(false))
+            .map_err(|_| Error::new_config("WebDAV 'disable_copy' 配置无效"))?;
+
+        Ok(Self::new(endpoint, WebdavAuth::from_config(config)?, root)
+            .with_disable_copy(disable_copy)
+            .with_retry(RetryPolicy::from_config(config)?)
+            .with_limits(ConnectionLimits::from_config(config)?))
+    }
+
+    /// 去掉协议头的端点，用于标识和显示
+    fn display_endpoint(&self) -> &str {
+        self.endpoint
+            .split_once("://")
+            .map(|(_, rest)| rest)
+            .unwrap_or(&self.endpoint)
+    }
+}
+
+impl Protocol for WebdavProtocol {
+    fn create_operator(&self) -> Result<Operator> {
+        debug!(
+            "创建 WebDAV 操作符, endpoint: {}, disable_copy: {}",
+            self.endpoint, self.disable_copy
+        );
+
+        let mut config = WebdavConfig::default();
+        config.endpoint = Some(self.endpoint.clone());
+        config.root = self.root.clone();
+        config.disable_copy = self.disable_copy;
+        match &self.auth {
+            WebdavAuth::Anonymous => {}
+            WebdavAuth::Basic { username, password } => {
+                config.username = Some(username.clone());
+                config.password = Some(password.clone()).filter(|p| !p.is_empty());
+            }
+            WebdavAuth::Bearer(token) => config.token = Some(token.clone()),
+        }
+
+        let op = Operator::from_config(config)?.finish();
+
+        Ok(apply_layers(op, &self.retry, &self.limits, "webdav"))
+    }
+
+    fn get_id(&self) -> String {
+        match &self.auth {
+            WebdavAuth::Basic { username, .. } => {
+                format!("webdav://{}@{}", username, self.display_endpoint())
+            }
+            _ => format!("webdav://{}", self.display_endpoint()),
+        }
+    }
+
+    fn get_name(&self) -> String {
+        format!("WebDAV ({})", self.display_endpoint())
+    }
+
+    fn get_capabilities(&self) -> Capabilities {
+        Capabilities::default()
+            .with_list(true)
+            .with_read(true)
+            .with_write(true)
+            .with_create_dir(true)
+            .with_delete(true)
+            .with_copy(!self.disable_copy)
+            .with_rename(true)
+            .with_batch_delete(false)
+    }
+
+    fn get_rate_limit(&self) -> Option<u64> {
+        self.limits.rate_limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_config() -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert(
+            "endpoint".to_string(),
+            "https://cloud.example.com/remote.php/dav/files/alice/".to_string(),
+        );
+        config
+    }
+
+    #[test]
+    fn test_webdav_protocol_from_config_basic() {
+        let mut config = base_config();
+        config.insert("username".to_string(), "alice".to_string());
+        config.insert("password".to_string(), "app-password".to_string());
+        config.insert("root".to_string(), "/Documents".to_string());
+
+        let protocol = WebdavProtocol::from_config(&config).unwrap();
+        assert_eq!(
+            protocol.endpoint,
+            "https://cloud.example.com/remote.php/dav/files/alice"
+        );
+        assert_eq!(
+            protocol.auth,
+            WebdavAuth::Basic {
+                username: "alice".to_string(),
+                password: "app-password".to_string()
+            }
+        );
+        assert_eq!(protocol.root, Some("/Documents".to_string()));
+        assert!(!protocol.disable_copy);
+    }
+
+    #[test]
+    fn test_webdav_protocol_from_config_token() {
+        let mut config = base_config();
+        config.insert(TOKEN_KEY.to_string(), "secret".to_string());
+        config.insert(DISABLE_COPY_KEY.to_string(), "true".to_string());
+
+        let protocol = WebdavProtocol::from_config(&config).unwrap();
+        assert_eq!(protocol.auth, WebdavAuth::Bearer("secret".to_string()));
+        assert!(!protocol.get_capabilities().can_copy);
+
+        config.insert("username".to_string(), "alice".to_string());
+        assert!(WebdavProtocol::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_webdav_protocol_invalid_config() {
+        assert!(WebdavProtocol::from_config(&HashMap::new()).is_err());
+
+        let mut config = HashMap::new();
+        config.insert("endpoint".to_string(), "cloud.example.com".to_string());
+        assert!(WebdavProtocol::from_config(&config).is_err());
+
+        let mut config = base_config();
+        config.insert(DISABLE_COPY_KEY.to_string(), "maybe".to_string());
+        assert!(WebdavProtocol::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_webdav_protocol_id_and_name() {
+        let protocol = WebdavProtocol::new(
+            "https://dav.example.com/".to_string(),
+            WebdavAuth::Basic {
+                username: "bob".to_string(),
+                password: "pass".to_string(),
+            },
+            None,
+        );
+        assert_eq!(protocol.get_id(), "webdav://bob@dav.example.com");
+        assert_eq!(protocol.get_name(), "WebDAV (dav.example.com)");
+
+        let protocol = WebdavProtocol::new(
+            "http://127.0.0.1:8080".to_string(),
+            WebdavAuth::Anonymous,
+            None,
+        );
+        assert_eq!(protocol.get_id(), "webdav://127.0.0.1:8080");
+        assert!(protocol.get_capabilities().can_copy);
+    }
+}
@@ -20,4 +20,41 @@ pub mod format {
             format!("{:.2} TB", size as f64 / TB as f64)
         }
     }
+
+    /// 解析带单位的大小字符串，如 "512"、"64K"、"5M"、"1.5G"（1024 进制）
+    pub fn parse_size(input: &str) -> Option<u64> {
+        let input = input.trim();
+        let split = input
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(input.len());
+        let (number, unit) = input.split_at(split);
+        let number: f64 = number.parse().ok()?;
+
+        let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" | "KIB" => 1024,
+            "M" | "MB" | "MIB" => 1024 * 1024,
+            "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+            "T" | "TB" | "TIB" => 1024 * 1024 * 1024 * 1024,
+            _ => return None,
+        };
+
+        Some((number * multiplier as f64) as u64)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_parse_size() {
+            assert_eq!(parse_size("512"), Some(512));
+            assert_eq!(parse_size("64K"), Some(64 * 1024));
+            assert_eq!(parse_size("5M"), Some(5 * 1024 * 1024));
+            assert_eq!(parse_size("1.5g"), Some(1536 * 1024 * 1024));
+            assert_eq!(parse_size("16MiB"), Some(16 * 1024 * 1024));
+            assert_eq!(parse_size("abc"), None);
+            assert_eq!(parse_size("5X"), None);
+        }
+    }
 }
@@ -0,0 +1,296 @@
+use multi_protocol_file_manager::protocols::{azblob::AzblobProtocol, create_protocol, Protocol};
+use std::collections::HashMap;
+
+/// 基础单元测试，不需要真实的Azure Blob服务
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_create_azblob_protocol_via_factory() {
+        let mut config = HashMap::new();
+        config.insert("container".to_string(), "testcontainer".to_string());
+        config.insert(
+            "connection_string".to_string(),
+            "UseDevelopmentStorage=true".to_string(),
+        );
+
+        let protocol = create_protocol("azblob", &config).unwrap();
+        assert_eq!(protocol.get_id(), "azblob:testcontainer");
+        assert!(protocol.get_name().contains("Azure Blob"));
+        assert!(protocol.create_operator().is_ok());
+    }
+
+    #[test]
+    fn test_azblob_protocol_config_validation() {
+        // 缺少 container
+        let mut config = HashMap::new();
+        config.insert("account_name".to_string(), "myaccount".to_string());
+        config.insert("account_key".to_string(), "a2V5".to_string());
+        assert!(create_protocol("azblob", &config).is_err());
+
+        // 缺少认证信息
+        let mut config = HashMap::new();
+        config.insert("container".to_string(), "data".to_string());
+        config.insert("account_name".to_string(), "myaccount".to_string());
+        assert!(create_protocol("azblob", &config).is_err());
+
+        // SAS 认证
+        config.insert("sas_token".to_string(), "sv=2022-11-02&sig=abc".to_string());
+        assert!(create_protocol("azblob", &config).is_ok());
+    }
+
+    #[test]
+    fn test_azblob_protocol_capabilities() {
+        let mut config = HashMap::new();
+        config.insert("container".to_string(), "data".to_string());
+        config.insert("account_name".to_string(), "myaccount".to_string());
+        config.insert("account_key".to_string(), "a2V5".to_string());
+
+        let protocol = create_protocol("azblob", &config).unwrap();
+        let caps = protocol.get_capabilities();
+
+        assert!(caps.can_list, "应该支持列出目录内容");
+        assert!(caps.can_read, "应该支持读取文件");
+        assert!(caps.can_write, "应该支持写入文件");
+        assert!(caps.can_delete, "应该支持删除文件");
+        assert!(caps.can_create_dir, "应该支持创建目录");
+        assert!(caps.can_copy, "Azure Blob支持服务器端复制");
+        assert!(caps.can_batch_delete, "Azure Blob支持批量删除");
+        assert!(!caps.can_rename, "Azure Blob没有重命名操作");
+    }
+}
+
+/// 集成测试，需要一个可用的Azurite模拟器
+/// 使用 ./scripts/setup_azblob_test.sh 启动模拟器并创建测试容器
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use multi_protocol_file_manager::core::FileManager;
+    use opendal::EntryMode;
+
+    // 辅助函数：创建测试用的Azure Blob配置
+    pub fn create_test_azblob_config() -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("container".to_string(), "testcontainer".to_string());
+        config.insert(
+            "connection_string".to_string(),
+            "UseDevelopmentStorage=true".to_string(),
+        );
+        config.insert("root".to_string(), "/upload".to_string());
+        config
+    }
+
+    // 创建带有协议能力描述的文件管理器，检查模拟器是否可用
+    async fn create_test_file_manager() -> Option<FileManager> {
+        // 探测时不重试，模拟器未启动时尽快跳过
+        let mut probe_config = create_test_azblob_config();
+        probe_config.insert("retry_max_attempts".to_string(), "1".to_string());
+        let probe = AzblobProtocol::from_config(&probe_config).ok()?;
+        probe.create_operator().ok()?.list("/").await.ok()?;
+
+        let protocol = AzblobProtocol::from_config(&create_test_azblob_config()).ok()?;
+        let operator = protocol.create_operator().ok()?;
+        Some(FileManager::new(operator).with_capabilities(protocol.get_capabilities()))
+    }
+
+    #[tokio::test]
+    async fn test_azblob_file_operations() {
+        let Some(manager) = create_test_file_manager().await else {
+            println!("跳过Azure Blob集成测试：没有可用的Azurite模拟器");
+            println!("请运行 ./scripts/setup_azblob_test.sh 启动测试服务器");
+            return;
+        };
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let local_path = temp_dir.path().join("upload.txt");
+        let test_content = "Hello, Azure Blob!";
+        std::fs::write(&local_path, test_content).unwrap();
+
+        // 上传与下载
+        let upload_result = manager.upload(&local_path, "files/test_file.txt").await;
+        assert!(
+            upload_result.is_ok(),
+            "上传文件失败: {:?}",
+            upload_result.err()
+        );
+
+        let download_path = temp_dir.path().join("download.txt");
+        let download_result = manager
+            .download("files/test_file.txt", &download_path)
+            .await;
+        assert!(
+            download_result.is_ok(),
+            "下载文件失败: {:?}",
+            download_result.err()
+        );
+        assert_eq!(
+            std::fs::read_to_string(&download_path).unwrap(),
+            test_content
+        );
+
+        // 获取文件信息
+        let meta = manager.stat("files/test_file.txt").await.unwrap();
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), test_content.len() as u64);
+
+        // 列出目录
+        let entries = manager.list("files/").await.unwrap();
+        assert!(
+            entries.iter().any(|e| e.name() == "test_file.txt"),
+            "未找到测试文件"
+        );
+
+        // 服务器端复制
+        manager
+            .copy("files/test_file.txt", "files/copied.txt")
+            .await
+            .unwrap();
+        assert_eq!(
+            manager
+                .read_file("files/copied.txt")
+                .await
+                .unwrap()
+                .to_vec(),
+            test_content.as_bytes()
+        );
+
+        // 不支持重命名时以复制后删除的方式移动
+        manager
+            .rename("files/copied.txt", "moved/renamed.txt")
+            .await
+            .unwrap();
+        assert!(
+            manager.stat("files/copied.txt").await.is_err(),
+            "原文件应该已被删除"
+        );
+        assert!(manager.stat("moved/renamed.txt").await.is_ok());
+
+        // 单个文件删除
+        manager.delete("moved/renamed.txt").await.unwrap();
+        assert!(manager.stat("moved/renamed.txt").await.is_err());
+
+        // 清理
+        manager.delete_recursive("files/").await.unwrap();
+        let _ = manager.delete_recursive("moved/").await;
+    }
+
+    #[tokio::test]
+    async fn test_azblob_directory_operations() {
+        let Some(manager) = create_test_file_manager().await else {
+            println!("跳过Azure Blob目录操作测试：没有可用的Azurite模拟器");
+            println!("请运行 ./scripts/setup_azblob_test.sh 启动测试服务器");
+            return;
+        };
+
+        // 创建目录
+        let create_result = manager.create_dir("test_directory/").await;
+        assert!(
+            create_result.is_ok(),
+            "创建目录失败: {:?}",
+            create_result.err()
+        );
+        let entries = manager.list("/").await.unwrap();
+        let found_test_dir = entries
+            .iter()
+            .any(|e| e.name() == "test_directory/" && e.metadata().mode() == EntryMode::DIR);
+        assert!(found_test_dir, "未找到测试目录");
+
+        // 上传一个目录树
+        let temp_dir = tempfile::tempdir().unwrap();
+        let local_root = temp_dir.path().join("tree");
+        std::fs::create_dir_all(local_root.join("nested")).unwrap();
+        std::fs::write(local_root.join("a.txt"), "a").unwrap();
+        std::fs::write(local_root.join("nested/b.txt"), "b").unwrap();
+
+        let report = manager
+            .upload_dir(&local_root, "test_directory/tree", 4, |_| {})
+            .await
+            .unwrap();
+        assert_eq!(report.failed().count(), 0, "上传目录失败: {:?}", report);
+
+        let files = manager
+            .list_files_recursive("test_directory/tree/")
+            .await
+            .unwrap();
+        assert_eq!(files.len(), 2, "递归列出的文件数量不正确: {:?}", files);
+
+        // 递归复制目录
+        manager
+            .copy("test_directory/tree/", "test_directory/tree_copy/")
+            .await
+            .unwrap();
+        assert_eq!(
+            manager
+                .read_file("test_directory/tree_copy/nested/b.txt")
+                .await
+                .unwrap()
+                .to_vec(),
+            b"b"
+        );
+
+        // 批量删除整个目录
+        let delete_result = manager.delete_recursive("test_directory/").await;
+        assert!(
+            delete_result.is_ok(),
+            "删除目录失败: {:?}",
+            delete_result.err()
+        );
+        assert!(manager
+            .list_files_recursive("test_directory/")
+            .await
+            .map(|files| files.is_empty())
+            .unwrap_or(true));
+    }
+
+    #[tokio::test]
+    async fn test_azblob_large_file_operations() {
+        let Some(manager) = create_test_file_manager().await else {
+            println!("跳过Azure Blob大文件测试：没有可用的Azurite模拟器");
+            return;
+        };
+        // 使用较小的块大小，让上传分成多个 block
+        let manager = manager.with_chunk_size(1024 * 1024);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let local_path = temp_dir.path().join("large.bin");
+        let large_content: Vec<u8> = (0..5 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        std::fs::write(&local_path, &large_content).unwrap();
+
+        let upload_result = manager.upload(&local_path, "large_test_file.bin").await;
+        assert!(
+            upload_result.is_ok(),
+            "上传大文件失败: {:?}",
+            upload_result.err()
+        );
+
+        let content = manager.read_file("large_test_file.bin").await.unwrap();
+        assert_eq!(content.len(), large_content.len());
+        assert_eq!(content.to_vec(), large_content);
+
+        let _ = manager.delete("large_test_file.bin").await;
+    }
+
+    #[tokio::test]
+    async fn test_azblob_wrong_account_key() {
+        if create_test_file_manager().await.is_none() {
+            println!("跳过Azure Blob认证失败测试：没有可用的Azurite模拟器");
+            return;
+        }
+
+        let mut config = create_test_azblob_config();
+        config.insert(
+            "connection_string".to_string(),
+            "AccountName=devstoreaccount1;AccountKey=d3JvbmdrZXk=;BlobEndpoint=http://127.0.0.1:10000/devstoreaccount1".to_string(),
+        );
+        let operator = AzblobProtocol::from_config(&config)
+            .unwrap()
+            .create_operator()
+            .unwrap();
+
+        assert!(
+            operator.list("/").await.is_err(),
+            "错误的密钥不应该能列出容器"
+        );
+    }
+}
@@ -0,0 +1,637 @@
+use multi_protocol_file_manager::protocols::{create_protocol, sftp::SftpProtocol, Protocol};
+use std::collections::HashMap;
+
+/// 基础单元测试，不需要真实的SFTP服务器
+#[cfg(test)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_create_sftp_protocol_via_factory() {
+        let mut config = HashMap::new();
+        config.insert("host".to_string(), "127.0.0.1".to_string());
+        config.insert("port".to_string(), "2222".to_string());
+        config.insert("username".to_string(), "testuser".to_string());
+        config.insert("password".to_string(), "testpass".to_string());
+
+        let protocol = create_protocol("sftp", &config).unwrap();
+        assert_eq!(protocol.get_id(), "sftp://testuser@127.0.0.1:2222");
+        assert!(protocol.get_name().contains("SFTP"));
+
+        let caps = protocol.get_capabilities();
+        assert!(caps.can_list);
+        assert!(caps.can_read);
+        assert!(caps.can_write);
+    }
+
+    #[test]
+    fn test_sftp_protocol_config_validation() {
+        // 既没有密码也没有私钥
+        let mut config = HashMap::new();
+        config.insert("host".to_string(), "127.0.0.1".to_string());
+        config.insert("username".to_string(), "testuser".to_string());
+
+        let result = create_protocol("sftp", &config);
+        assert!(result.is_err());
+
+        // 私钥认证不需要密码
+        config.insert("private_key".to_string(), "/path/to/id_ed25519".to_string());
+        assert!(create_protocol("sftp", &config).is_ok());
+    }
+
+    #[test]
+    fn test_sftp_protocol_invalid_port() {
+        let mut config = HashMap::new();
+        config.insert("host".to_string(), "127.0.0.1".to_string());
+        config.insert("port".to_string(), "invalid_port".to_string());
+        config.insert("username".to_string(), "testuser".to_string());
+        config.insert("password".to_string(), "testpass".to_string());
+
+        let result = create_protocol("sftp", &config);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sftp_protocol_invalid_known_hosts_strategy() {
+        let mut config = HashMap::new();
+        config.insert("host".to_string(), "127.0.0.1".to_string());
+        config.insert("username".to_string(), "testuser".to_string());
+        config.insert("password".to_string(), "testpass".to_string());
+        config.insert("known_hosts_strategy".to_string(), "maybe".to_string());
+
+        let result = create_protocol("sftp", &config);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_sftp_protocol_capabilities() {
+        let mut config = HashMap::new();
+        config.insert("host".to_string(), "sftp.example.com".to_string());
+        config.insert("username".to_string(), "user".to_string());
+        config.insert("password".to_string(), "pass".to_string());
+
+        let protocol = create_protocol("sftp", &config).unwrap();
+        let caps = protocol.get_capabilities();
+
+        assert!(caps.can_list, "应该支持列出目录内容");
+        assert!(caps.can_read, "应该支持读取文件");
+        assert!(caps.can_write, "应该支持写入文件");
+        assert!(caps.can_delete, "应该支持删除文件");
+        assert!(caps.can_create_dir, "应该支持创建目录");
+        assert!(caps.can_rename, "应该支持重命名文件");
+        assert!(!caps.can_copy, "SFTP协议不支持服务器端复制");
+        assert!(!caps.can_batch_delete, "SFTP协议不支持批量删除");
+    }
+}
+
+/// 集成测试，需要一个可用的SFTP服务器
+/// 使用 ./scripts/setup_sftp_test.sh 启动测试服务器
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use opendal::{EntryMode, ErrorKind};
+    use std::path::PathBuf;
+
+    // 辅助函数：创建测试用的SFTP配置
+    // 测试服务器每次启动都会生成新的主机密钥，因此不校验主机密钥
+    pub fn create_test_sftp_config() -> HashMap<String, String> {
+        let mut config = HashMap::new();
+        config.insert("host".to_string(), "127.0.0.1".to_string());
+        config.insert("port".to_string(), "2222".to_string());
+        config.insert("username".to_string(), "testuser".to_string());
+        config.insert("password".to_string(), "testpass".to_string());
+        config.insert("root".to_string(), "upload".to_string());
+        config.insert("known_hosts_strategy".to_string(), "accept".to_string());
+        config
+    }
+
+    // setup 脚本生成的测试私钥
+    fn test_private_key() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target/sftp-test/id_ed25519")
+    }
+
+    // 检查是否有可用的SFTP服务器
+    async fn check_sftp_server_available() -> bool {
+        let config = create_test_sftp_config();
+        let protocol = match SftpProtocol::from_config(&config) {
+            Ok(p) => p,
+            Err(_) => return false,
+        };
+
+        let operator = match protocol.create_operator() {
+            Ok(op) => op,
+            Err(_) => return false,
+        };
+
+        // 尝试列出根目录来测试连接
+        operator.list("/").await.is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_sftp_operator_creation() {
+        let config = create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+
+        // 创建操作符应该成功，即使没有连接到服务器
+        let result = protocol.create_operator();
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_sftp_basic_operations() {
+        // 只有在有可用的SFTP服务器时才运行此测试
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP集成测试：没有可用的SFTP服务器");
+            println!("请运行 ./scripts/setup_sftp_test.sh 启动测试服务器");
+            return;
+        }
+
+        let config = create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        let operator = protocol.create_operator().unwrap();
+
+        // 测试写入文件
+        let test_content = "Hello, SFTP World!";
+        let test_path = "test_file.txt";
+
+        let write_result = operator.write(test_path, test_content).await;
+        assert!(
+            write_result.is_ok(),
+            "写入文件失败: {:?}",
+            write_result.err()
+        );
+
+        // 测试读取文件
+        let read_result = operator.read(test_path).await;
+        assert!(read_result.is_ok(), "读取文件失败: {:?}", read_result.err());
+
+        let content = read_result.unwrap();
+        assert_eq!(content.to_vec(), test_content.as_bytes());
+
+        // 测试读取部分内容
+        let range_result = operator.read_with(test_path).range(7..11).await;
+        assert!(
+            range_result.is_ok(),
+            "按范围读取失败: {:?}",
+            range_result.err()
+        );
+        assert_eq!(range_result.unwrap().to_vec(), b"SFTP");
+
+        // 测试获取文件信息
+        let stat_result = operator.stat(test_path).await;
+        assert!(
+            stat_result.is_ok(),
+            "获取文件信息失败: {:?}",
+            stat_result.err()
+        );
+        let meta = stat_result.unwrap();
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), test_content.len() as u64);
+
+        // 测试列出目录
+        let list_result = operator.list("/").await;
+        assert!(list_result.is_ok(), "列出目录失败: {:?}", list_result.err());
+
+        let entries = list_result.unwrap();
+        let found_test_file = entries
+            .iter()
+            .any(|entry| entry.name() == test_path && entry.metadata().mode() == EntryMode::FILE);
+        assert!(found_test_file, "未找到测试文件");
+
+        // 测试删除文件
+        let delete_result = operator.delete(test_path).await;
+        assert!(
+            delete_result.is_ok(),
+            "删除文件失败: {:?}",
+            delete_result.err()
+        );
+
+        // 验证文件已被删除
+        let read_after_delete = operator.read(test_path).await;
+        assert!(read_after_delete.is_err(), "文件删除后仍然可以读取");
+    }
+
+    #[tokio::test]
+    async fn test_sftp_directory_operations() {
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP目录操作测试：没有可用的SFTP服务器");
+            println!("请运行 ./scripts/setup_sftp_test.sh 启动测试服务器");
+            return;
+        }
+
+        let config = create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        let operator = protocol.create_operator().unwrap();
+
+        let test_dir = "test_directory/";
+
+        // 测试创建目录
+        let create_dir_result = operator.create_dir(test_dir).await;
+        assert!(
+            create_dir_result.is_ok(),
+            "创建目录失败: {:?}",
+            create_dir_result.err()
+        );
+
+        // 测试列出根目录，应该包含新创建的目录
+        let list_result = operator.list("/").await;
+        assert!(list_result.is_ok());
+
+        let entries = list_result.unwrap();
+        let found_test_dir = entries.iter().any(|entry| {
+            entry.name() == "test_directory/" && entry.metadata().mode() == EntryMode::DIR
+        });
+        assert!(found_test_dir, "未找到测试目录");
+
+        // 在目录中创建文件，父目录不存在时自动创建
+        let file_in_dir = "test_directory/nested/file_in_dir.txt";
+        let file_content = "File in directory";
+
+        let write_result = operator.write(file_in_dir, file_content).await;
+        assert!(
+            write_result.is_ok(),
+            "在目录中写入文件失败: {:?}",
+            write_result.err()
+        );
+
+        // 读取目录中的文件
+        let read_result = operator.read(file_in_dir).await;
+        assert!(read_result.is_ok());
+        assert_eq!(read_result.unwrap().to_vec(), file_content.as_bytes());
+
+        // 清理：递归删除目录
+        let delete_dir_result = operator.remove_all(test_dir).await;
+        assert!(
+            delete_dir_result.is_ok(),
+            "删除目录失败: {:?}",
+            delete_dir_result.err()
+        );
+
+        let stat_after_delete = operator.stat(test_dir).await;
+        assert!(stat_after_delete.is_err(), "目录删除后仍然存在");
+    }
+
+    #[tokio::test]
+    async fn test_sftp_large_file_operations() {
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP大文件测试：没有可用的SFTP服务器");
+            println!("请运行 ./scripts/setup_sftp_test.sh 启动测试服务器");
+            return;
+        }
+
+        let config = create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        let operator = protocol.create_operator().unwrap();
+
+        // 创建一个较大的测试内容（约5MB，超过单次读取的块大小）
+        let large_content: Vec<u8> = (0..5 * 1024 * 1024).map(|i| (i % 251) as u8).collect();
+        let test_path = "large_test_file.bin";
+
+        // 写入大文件
+        let write_result = operator.write(test_path, large_content.clone()).await;
+        assert!(
+            write_result.is_ok(),
+            "写入大文件失败: {:?}",
+            write_result.err()
+        );
+
+        // 读取大文件
+        let read_result = operator.read(test_path).await;
+        assert!(
+            read_result.is_ok(),
+            "读取大文件失败: {:?}",
+            read_result.err()
+        );
+
+        let content = read_result.unwrap();
+        assert_eq!(content.len(), large_content.len());
+        assert_eq!(content.to_vec(), large_content);
+
+        // 清理
+        let _ = operator.delete(test_path).await;
+    }
+
+    #[tokio::test]
+    async fn test_sftp_file_rename() {
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP文件重命名测试：没有可用的SFTP服务器");
+            println!("请运行 ./scripts/setup_sftp_test.sh 启动测试服务器");
+            return;
+        }
+
+        let config = create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        let operator = protocol.create_operator().unwrap();
+
+        let original_content = "File to be renamed";
+        let original_path = "original_file.txt";
+        let new_path = "renamed/renamed_file.txt";
+
+        // 写入原文件，目标位置预先放一个旧文件，重命名应覆盖它
+        let write_result = operator.write(original_path, original_content).await;
+        assert!(
+            write_result.is_ok(),
+            "写入原文件失败: {:?}",
+            write_result.err()
+        );
+        let write_old_result = operator.write(new_path, "stale").await;
+        assert!(
+            write_old_result.is_ok(),
+            "写入旧文件失败: {:?}",
+            write_old_result.err()
+        );
+
+        // 服务器端重命名
+        let rename_result = operator.rename(original_path, new_path).await;
+        assert!(
+            rename_result.is_ok(),
+            "重命名失败: {:?}",
+            rename_result.err()
+        );
+
+        // 验证新文件存在且内容正确
+        let read_new_result = operator.read(new_path).await;
+        assert!(read_new_result.is_ok(), "读取新文件失败");
+        assert_eq!(
+            read_new_result.unwrap().to_vec(),
+            original_content.as_bytes()
+        );
+
+        // 验证原文件不存在
+        let read_old_result = operator.read(original_path).await;
+        assert!(read_old_result.is_err(), "原文件应该已被删除");
+
+        // 清理
+        let _ = operator.remove_all("renamed/").await;
+    }
+
+    #[tokio::test]
+    async fn test_sftp_failed_rename_keeps_target() {
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP重命名失败测试：没有可用的SFTP服务器");
+            return;
+        }
+
+        let config = create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        let operator = protocol.create_operator().unwrap();
+
+        let target_path = "rename_failure/target.txt";
+        operator.write(target_path, "keep me").await.unwrap();
+
+        // 源文件不存在，重命名失败后原有的目标文件应该保持不变
+        let rename_result = operator
+            .rename("rename_failure/missing.txt", target_path)
+            .await;
+        assert!(rename_result.is_err(), "源文件不存在时重命名应该失败");
+        assert_eq!(
+            operator.read(target_path).await.unwrap().to_vec(),
+            b"keep me"
+        );
+
+        // 不应该留下暂存的临时文件
+        let entries = operator.list("rename_failure/").await.unwrap();
+        let names: Vec<&str> = entries
+            .iter()
+            .map(|e| e.name())
+            .filter(|name| *name != "rename_failure/")
+            .collect();
+        assert_eq!(names, vec!["target.txt"], "目录中有多余的文件: {:?}", names);
+
+        // 清理
+        let _ = operator.remove_all("rename_failure/").await;
+    }
+
+    #[tokio::test]
+    async fn test_sftp_error_handling() {
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP错误处理测试：没有可用的SFTP服务器");
+            println!("请运行 ./scripts/setup_sftp_test.sh 启动测试服务器");
+            return;
+        }
+
+        let config = create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+        let operator = protocol.create_operator().unwrap();
+
+        // 测试读取不存在的文件
+        let read_result = operator.read("nonexistent_file.txt").await;
+        assert_eq!(
+            read_result.err().map(|e| e.kind()),
+            Some(ErrorKind::NotFound)
+        );
+
+        // 删除不存在的文件视为成功
+        let delete_result = operator.delete("nonexistent_file.txt").await;
+        assert!(delete_result.is_ok(), "删除不存在的文件应该成功");
+
+        // 列出不存在的目录得到空结果
+        let list_result = operator.list("nonexistent_dir/").await;
+        assert!(list_result.is_ok(), "列出不存在的目录应该返回空结果");
+
+        // 重复创建同一目录应该成功
+        let test_dir = "existing_test_dir/";
+        assert!(operator.create_dir(test_dir).await.is_ok());
+        assert!(operator.create_dir(test_dir).await.is_ok());
+
+        // 清理
+        let _ = operator.delete(test_dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_sftp_wrong_password() {
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP认证失败测试：没有可用的SFTP服务器");
+            return;
+        }
+
+        let mut config = create_test_sftp_config();
+        config.insert("password".to_string(), "wrongpass".to_string());
+        let operator = SftpProtocol::from_config(&config)
+            .unwrap()
+            .create_operator()
+            .unwrap();
+
+        let result = operator.list("/").await;
+        assert_eq!(
+            result.err().map(|e| e.kind()),
+            Some(ErrorKind::PermissionDenied)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sftp_private_key_auth() {
+        let key = test_private_key();
+        if !key.exists() || !check_sftp_server_available().await {
+            println!("跳过SFTP私钥认证测试：没有可用的SFTP服务器或测试私钥");
+            println!("请运行 ./scripts/setup_sftp_test.sh 启动测试服务器");
+            return;
+        }
+
+        let mut config = create_test_sftp_config();
+        config.remove("password");
+        config.insert("private_key".to_string(), key.to_string_lossy().to_string());
+        let operator = SftpProtocol::from_config(&config)
+            .unwrap()
+            .create_operator()
+            .unwrap();
+
+        let list_result = operator.list("/").await;
+        assert!(list_result.is_ok(), "私钥认证失败: {:?}", list_result.err());
+    }
+
+    #[tokio::test]
+    async fn test_sftp_known_hosts_verification() {
+        if !check_sftp_server_available().await {
+            println!("跳过SFTP主机密钥校验测试：没有可用的SFTP服务器");
+            return;
+        }
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let known_hosts = temp_dir.path().join("known_hosts");
+        let mut config = create_test_sftp_config();
+        config.insert(
+            "known_hosts".to_string(),
+            known_hosts.to_string_lossy().to_string(),
+        );
+
+        // strict：known_hosts 中没有记录时拒绝连接
+        config.insert("known_hosts_strategy".to_string(), "strict".to_string());
+        let operator = SftpProtocol::from_config(&config)
+            .unwrap()
+            .create_operator()
+            .unwrap();
+        let result = operator.list("/").await;
+        assert_eq!(
+            result.err().map(|e| e.kind()),
+            Some(ErrorKind::PermissionDenied)
+        );
+
+        // add：首次连接记录主机密钥
+        config.insert("known_hosts_strategy".to_string(), "add".to_string());
+        let operator = SftpProtocol::from_config(&config)
+            .unwrap()
+            .create_operator()
+            .unwrap();
+        assert!(operator.list("/").await.is_ok());
+        let recorded = std::fs::read_to_string(&known_hosts).unwrap();
+        assert!(
+            recorded
+                .lines()
+                .any(|line| line.starts_with("[127.0.0.1]:2222 ")),
+            "未记录主机密钥: {}",
+            recorded
+        );
+
+        // 记录之后 strict 也能连接
+        config.insert("known_hosts_strategy".to_string(), "strict".to_string());
+        let operator = SftpProtocol::from_config(&config)
+            .unwrap()
+            .create_operator()
+            .unwrap();
+        assert!(operator.list("/").await.is_ok());
+    }
+}
+
+/// 性能测试
+#[cfg(test)]
+mod performance_tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_sftp_connection_time() {
+        let config = integration_tests::create_test_sftp_config();
+        let protocol = SftpProtocol::from_config(&config).unwrap();
+
+        let start = Instant::now();
+        let result = protocol.create_operator();
+        let duration = start.elapsed();
+
+        assert!(result.is_ok());
+        println!("SFTP操作符创建时间: {:?}", duration);
+        // 创建操作符时不连接服务器，应该很快完成
+        assert!(
+            duration.as_millis() < 1000,
+            "操作符创建时间过长: {:?}",
+            duration
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sftp_throughput() {
+        let config = integration_tests::create_test_sftp_config();
+        let protocol = match SftpProtocol::from_config(&config) {
+            Ok(p) => p,
+            Err(_) => {
+                println!("跳过SFTP吞吐量测试：配置无效");
+                return;
+            }
+        };
+
+        let operator = match protocol.create_operator() {
+            Ok(op) => op,
+            Err(_) => {
+                println!("跳过SFTP吞吐量测试：无法创建操作符");
+                return;
+            }
+        };
+
+        // 检查连接是否可用
+        if operator.list("/").await.is_err() {
+            println!("跳过SFTP吞吐量测试：没有可用的SFTP服务器");
+            println!("请运行 ./scripts/setup_sftp_test.sh 启动测试服务器");
+            return;
+        }
+
+        // 测试多个小文件的写入性能
+        let file_count = 10;
+        let file_size = 1024; // 1KB per file
+        let content = "X".repeat(file_size);
+
+        let start = Instant::now();
+
+        for i in 0..file_count {
+            let file_path = format!("perf_test_{}.txt", i);
+            let write_result = operator.write(&file_path, content.clone()).await;
+            assert!(write_result.is_ok(), "写入文件 {} 失败", file_path);
+        }
+
+        let write_duration = start.elapsed();
+        println!(
+            "写入 {} 个文件（每个{}字节）耗时: {:?}",
+            file_count, file_size, write_duration
+        );
+
+        // 测试读取性能
+        let start = Instant::now();
+
+        for i in 0..file_count {
+            let file_path = format!("perf_test_{}.txt", i);
+            let read_result = operator.read(&file_path).await;
+            assert!(read_result.is_ok(), "读取文件 {} 失败", file_path);
+            assert_eq!(read_result.unwrap().len(), file_size);
+        }
+
+        let read_duration = start.elapsed();
+        println!("读取 {} 个文件耗时: {:?}", file_count, read_duration);
+
+        // 清理测试文件
+        for i in 0..file_count {
+            let file_path = format!("perf_test_{}.txt", i);
+            let _ = operator.delete(&file_path).await;
+        }
+
+        // 性能断言（宽松的限制，主要是确保没有严重性能问题）
+        assert!(
+            write_duration.as_secs() < 30,
+            "写入性能过慢: {:?}",
+            write_duration
+        );
+        assert!(
+            read_duration.as_secs() < 30,
+            "读取性能过慢: {:?}",
+            read_duration
+        );
+    }
+}
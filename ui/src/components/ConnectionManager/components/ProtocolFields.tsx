@@ -1,6 +1,7 @@
 import React from 'react';
-import { Form, Input, Checkbox } from 'antd';
+import { Form, Input, Checkbox, Select } from 'antd';
 import { useAppI18n } from '../../../i18n/hooks/useI18n';
+import { S3BucketPicker } from './S3BucketPicker';
 
 /**
  * 渲染协议特定的表单字段
@@ -18,6 +19,11 @@ export const ProtocolFields: React.FC<{ protocolType: string }> = ({ protocolTyp
         >
           <Input placeholder={connection.fields.bucketNamePlaceholder} />
         </Form.Item>
+        <Form.Item>
+          <Form.Item name="extraBuckets" noStyle>
+            <S3BucketPicker />
+          </Form.Item>
+        </Form.Item>
         <Form.Item
           name="region"
           label={connection.fields.region}
@@ -32,18 +38,96 @@ export const ProtocolFields: React.FC<{ protocolType: string }> = ({ protocolTyp
           <Input placeholder={connection.fields.endpointPlaceholder} />
         </Form.Item>
         <Form.Item
-          name="accessKey"
-          label={connection.fields.accessKey}
-          rules={[{ required: true, message: connection.fields.accessKeyRequired }]}
+          name="addressingStyle"
+          label={connection.fields.addressingStyle}
+          tooltip={connection.fields.addressingStyleTooltip}
+          initialValue="auto"
+        >
+          <Select
+            options={[
+              { value: 'auto', label: connection.fields.addressingStyleAuto },
+              { value: 'path', label: connection.fields.addressingStylePath },
+              { value: 'virtual_host', label: connection.fields.addressingStyleVirtualHost },
+            ]}
+          />
+        </Form.Item>
+        <Form.Item
+          name="credentialSource"
+          label={connection.fields.credentialSource}
+          tooltip={connection.fields.credentialSourceTooltip}
+          initialValue="static"
         >
-          <Input placeholder={connection.fields.accessKeyPlaceholder} />
+          <Select
+            options={[
+              { value: 'static', label: connection.fields.credentialSourceStatic },
+              { value: 'env', label: connection.fields.credentialSourceEnv },
+              { value: 'profile', label: connection.fields.credentialSourceProfile },
+              { value: 'anonymous', label: connection.fields.credentialSourceAnonymous },
+            ]}
+          />
         </Form.Item>
         <Form.Item
-          name="secretKey"
-          label={connection.fields.secretKey}
-          rules={[{ required: true, message: connection.fields.secretKeyRequired }]}
+          noStyle
+          shouldUpdate={(prev, current) => prev.credentialSource !== current.credentialSource}
         >
-          <Input.Password placeholder={connection.fields.secretKeyPlaceholder} />
+          {({ getFieldValue }) => {
+            const credentialSource = getFieldValue('credentialSource') || 'static';
+            return (
+              <>
+                {credentialSource === 'static' && (
+                  <>
+                    <Form.Item
+                      name="accessKey"
+                      label={connection.fields.accessKey}
+                      rules={[{ required: true, message: connection.fields.accessKeyRequired }]}
+                    >
+                      <Input placeholder={connection.fields.accessKeyPlaceholder} />
+                    </Form.Item>
+                    <Form.Item
+                      name="secretKey"
+                      label={connection.fields.secretKey}
+                      rules={[{ required: true, message: connection.fields.secretKeyRequired }]}
+                    >
+                      <Input.Password placeholder={connection.fields.secretKeyPlaceholder} />
+                    </Form.Item>
+                    <Form.Item
+                      name="sessionToken"
+                      label={connection.fields.sessionToken}
+                      tooltip={connection.fields.sessionTokenTooltip}
+                    >
+                      <Input.Password placeholder={connection.fields.sessionTokenPlaceholder} />
+                    </Form.Item>
+                  </>
+                )}
+                {credentialSource === 'profile' && (
+                  <Form.Item
+                    name="profile"
+                    label={connection.fields.profile}
+                    tooltip={connection.fields.profileTooltip}
+                  >
+                    <Input placeholder="default" />
+                  </Form.Item>
+                )}
+                {credentialSource !== 'anonymous' && (
+                  <>
+                    <Form.Item
+                      name="roleArn"
+                      label={connection.fields.roleArn}
+                      tooltip={connection.fields.roleArnTooltip}
+                    >
+                      <Input placeholder="arn:aws:iam::123456789012:role/example" />
+                    </Form.Item>
+                    <Form.Item
+                      name="externalId"
+                      label={connection.fields.externalId}
+                    >
+                      <Input />
+                    </Form.Item>
+                  </>
+                )}
+              </>
+            );
+          }}
         </Form.Item>
       </>
     );
@@ -137,6 +221,94 @@ export const ProtocolFields: React.FC<{ protocolType: string }> = ({ protocolTyp
       </>
     );
   }
-  
+
+  if (protocolType === 'webdav') {
+    return (
+      <>
+        <Form.Item
+          name="endpoint"
+          label={connection.fields.endpoint}
+          rules={[
+            { required: true, message: connection.fields.webdavEndpointRequired },
+            { pattern: /^https?:\/\//, message: connection.fields.webdavEndpointInvalid },
+          ]}
+          tooltip={connection.fields.webdavEndpointTooltip}
+        >
+          <Input placeholder={connection.fields.webdavEndpointPlaceholder} />
+        </Form.Item>
+
+        <Form.Item
+          name="webdavAuth"
+          label={connection.fields.webdavAuth}
+          initialValue="basic"
+        >
+          <Select
+            options={[
+              { value: 'basic', label: connection.fields.webdavAuthBasic },
+              { value: 'token', label: connection.fields.webdavAuthToken },
+              { value: 'anonymous', label: connection.fields.webdavAuthAnonymous },
+            ]}
+          />
+        </Form.Item>
+
+        <Form.Item
+          noStyle
+          shouldUpdate={(prev, current) => prev.webdavAuth !== current.webdavAuth}
+        >
+          {({ getFieldValue }) => {
+            const webdavAuth = getFieldValue('webdavAuth') || 'basic';
+            return (
+              <>
+                {webdavAuth === 'basic' && (
+                  <>
+                    <Form.Item
+                      name="username"
+                      label={connection.fields.username}
+                      rules={[{ required: true, message: connection.fields.usernameRequired }]}
+                      tooltip={connection.fields.webdavUsernameTooltip}
+                    >
+                      <Input autoComplete="username" />
+                    </Form.Item>
+                    <Form.Item
+                      name="password"
+                      label={connection.fields.password}
+                    >
+                      <Input.Password autoComplete="current-password" />
+                    </Form.Item>
+                  </>
+                )}
+                {webdavAuth === 'token' && (
+                  <Form.Item
+                    name="token"
+                    label={connection.fields.token}
+                    rules={[{ required: true, message: connection.fields.tokenRequired }]}
+                  >
+                    <Input.Password placeholder={connection.fields.tokenPlaceholder} />
+                  </Form.Item>
+                )}
+              </>
+            );
+          }}
+        </Form.Item>
+
+        <Form.Item
+          name="root_dir"
+          label={connection.fields.rootDirectory}
+        >
+          <Input placeholder={connection.fields.webdavRootDirectoryPlaceholder} />
+        </Form.Item>
+
+        <Form.Item
+          name="disableCopy"
+          label={connection.fields.disableCopy}
+          valuePropName="checked"
+          tooltip={connection.fields.disableCopyTooltip}
+        >
+          <Checkbox>{connection.fields.disableCopy}</Checkbox>
+        </Form.Item>
+      </>
+    );
+  }
+
   return null;
 };
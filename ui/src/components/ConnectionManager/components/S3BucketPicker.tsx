@@ -0,0 +1,98 @@
+import React, { useState } from 'react';
+import { Button, Checkbox, Empty, Form, Modal, Spin, Typography, message } from 'antd';
+import { SearchOutlined } from '@ant-design/icons';
+import { ApiService } from '../../../services/api';
+import { BucketInfo } from '../../../types';
+import { buildConfig } from '../utils.tsx';
+import { useAppI18n } from '../../../i18n/hooks/useI18n';
+
+interface S3BucketPickerProps {
+  value?: BucketInfo[];
+  onChange?: (extraBuckets: BucketInfo[]) => void;
+}
+
+/**
+ * 用表单中填写的凭证发现可用的 bucket
+ *
+ * 第一个选中的 bucket 填入表单，其余的作为表单值保存，添加连接时各自创建一个连接。
+ */
+export const S3BucketPicker: React.FC<S3BucketPickerProps> = ({ value, onChange }) => {
+  const form = Form.useFormInstance();
+  const { s3, app } = useAppI18n();
+  const [open, setOpen] = useState(false);
+  const [loading, setLoading] = useState(false);
+  const [buckets, setBuckets] = useState<BucketInfo[]>([]);
+  const [selected, setSelected] = useState<string[]>([]);
+
+  const discover = async () => {
+    const values = form.getFieldsValue(true);
+    setOpen(true);
+    setLoading(true);
+    try {
+      const found = await ApiService.discoverS3Buckets(buildConfig({ ...values, protocolType: 's3' }));
+      setBuckets(found);
+      // 保留之前的选择
+      const previous = [values.bucket, ...(value || []).map(bucket => bucket.name)];
+      setSelected(previous.filter(name => found.some(bucket => bucket.name === name)));
+    } catch (error) {
+      message.error(`${s3.discoverFailed}: ${error}`);
+      setOpen(false);
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const confirm = () => {
+    const chosen = buckets.filter(bucket => selected.includes(bucket.name));
+    if (chosen.length > 0) {
+      const [first, ...rest] = chosen;
+      form.setFieldsValue({
+        bucket: first.name,
+        ...(first.region ? { region: first.region } : {}),
+      });
+      onChange?.(rest);
+    }
+    setOpen(false);
+  };
+
+  return (
+    <>
+      <Button icon={<SearchOutlined />} onClick={discover}>
+        {s3.discoverBuckets}
+      </Button>
+      {value && value.length > 0 && (
+        <Typography.Text type="secondary" style={{ marginLeft: 8 }}>
+          {s3.extraBucketsHint.replace('{count}', String(value.length))}
+        </Typography.Text>
+      )}
+      <Modal
+        title={s3.selectBuckets}
+        open={open}
+        onOk={confirm}
+        onCancel={() => setOpen(false)}
+        cancelText={app.cancel}
+      >
+        <Spin spinning={loading}>
+          {buckets.length === 0 ? (
+            !loading && <Empty description={s3.noBucketsFound} />
+          ) : (
+            <Checkbox.Group
+              value={selected}
+              onChange={checked => setSelected(checked as string[])}
+              style={{ display: 'flex', flexDirection: 'column', gap: 8 }}
+            >
+              {buckets.map(bucket => (
+                <Checkbox key={bucket.name} value={bucket.name}>
+                  {bucket.name}
+                  {bucket.region && (
+                    <Typography.Text type="secondary"> ({bucket.region})</Typography.Text>
+                  )}
+                </Checkbox>
+              ))}
+            </Checkbox.Group>
+          )}
+        </Spin>
+      </Modal>
+    </>
+  );
+};
@@ -43,8 +43,15 @@ export const useConnectionModal = (directories: any[]) => {
           bucket: connection.config.bucket,
           region: connection.config.region,
           endpoint: connection.config.endpoint,
+          addressingStyle: connection.config.addressing_style
+            ?? (connection.config.path_style === 'true' ? 'path' : 'auto'),
+          credentialSource: connection.config.credential_source || 'static',
           accessKey: connection.config.access_key,
           secretKey: connection.config.secret_key,
+          sessionToken: connection.config.session_token,
+          profile: connection.config.profile,
+          roleArn: connection.config.role_arn,
+          externalId: connection.config.external_id,
         };
       } else if (connection.protocol_type === 'fs') {
         initialValues = {
@@ -71,6 +78,19 @@ export const useConnectionModal = (directories: any[]) => {
           root_dir: connection.config.root_dir,
           secure: connection.config.secure === 'true',
         };
+      } else if (connection.protocol_type === 'webdav') {
+        initialValues = {
+          ...initialValues,
+          endpoint: connection.config.endpoint,
+          webdavAuth: connection.config.token
+            ? 'token'
+            : connection.config.username ? 'basic' : 'anonymous',
+          username: connection.config.username,
+          password: connection.config.password,
+          token: connection.config.token,
+          root_dir: connection.config.root_dir,
+          disableCopy: connection.config.disable_copy === 'true',
+        };
       }
     } else if (type === MODAL_TYPES.EDIT && connection) {
       // 找到原连接所在的目录（编辑模式需要显示目录信息）
@@ -94,8 +114,15 @@ export const useConnectionModal = (directories: any[]) => {
           bucket: connection.config.bucket,
           region: connection.config.region,
           endpoint: connection.config.endpoint,
+          addressingStyle: connection.config.addressing_style
+            ?? (connection.config.path_style === 'true' ? 'path' : 'auto'),
+          credentialSource: connection.config.credential_source || 'static',
           accessKey: connection.config.access_key,
           secretKey: connection.config.secret_key,
+          sessionToken: connection.config.session_token,
+          profile: connection.config.profile,
+          roleArn: connection.config.role_arn,
+          externalId: connection.config.external_id,
         };
       } else if (connection.protocol_type === 'fs') {
         initialValues = {
@@ -122,6 +149,19 @@ export const useConnectionModal = (directories: any[]) => {
           root_dir: connection.config.root_dir,
           secure: connection.config.secure === 'true',
         };
+      } else if (connection.protocol_type === 'webdav') {
+        initialValues = {
+          ...initialValues,
+          endpoint: connection.config.endpoint,
+          webdavAuth: connection.config.token
+            ? 'token'
+            : connection.config.username ? 'basic' : 'anonymous',
+          username: connection.config.username,
+          password: connection.config.password,
+          token: connection.config.token,
+          root_dir: connection.config.root_dir,
+          disableCopy: connection.config.disable_copy === 'true',
+        };
       }
     }
     
@@ -3,6 +3,7 @@ import { message, Modal } from 'antd';
 import { ApiService } from '../../../services/api';
 import { MODAL_TYPES, ModalConfig, DirectoryItem } from '../types';
 import { buildConfig } from '../utils.tsx';
+import { BucketInfo } from '../../../types';
 import { useAppI18n } from '../../../i18n/hooks/useI18n';
 
 /**
@@ -18,17 +19,11 @@ export const useConnectionOperations = (
   const { connection: i18nConnection, s3, app } = useAppI18n();
   
   // S3 bucket 检查和创建
-  const checkAndCreateS3Bucket = useCallback(async (values: any): Promise<boolean> => {
+  const checkAndCreateS3Bucket = useCallback(async (values: any, config: Record<string, string>): Promise<boolean> => {
     if (values.protocolType !== 's3') return true;
 
     try {
-      const bucketExists = await ApiService.checkS3BucketExists(
-        values.bucket,
-        values.region,
-        values.endpoint || null,
-        values.accessKey,
-        values.secretKey
-      );
+      const bucketExists = await ApiService.checkS3BucketExists(config);
 
       if (!bucketExists) {
         return new Promise((resolve) => {
@@ -39,13 +34,7 @@ export const useConnectionOperations = (
             cancelText: app.cancel,
             onOk: async () => {
               try {
-                await ApiService.createS3Bucket(
-                  values.bucket,
-                  values.region,
-                  values.endpoint || null,
-                  values.accessKey,
-                  values.secretKey
-                );
+                await ApiService.createS3Bucket(config);
                 message.success(s3.bucketCreateSuccess);
                 resolve(true);
               } catch (error) {
@@ -67,6 +56,30 @@ export const useConnectionOperations = (
     }
   }, []);
 
+  // 自动检测时依次尝试路径样式和虚拟主机样式，保存实际可用的方式
+  const detectS3AddressingStyle = useCallback(async (values: any, config: Record<string, string>) => {
+    if (values.protocolType !== 's3' || config.addressing_style !== 'auto') return;
+
+    try {
+      config.addressing_style = await ApiService.detectS3AddressingStyle(config);
+    } catch (error) {
+      // 探测失败时保留自动模式，由后端按端点推断
+      console.warn('S3 addressing style detection failed:', error);
+    }
+  }, []);
+
+  // 发现 bucket 时选中了多个：为其余每个 bucket 各添加一个连接，返回新连接的 ID
+  const addExtraS3Connections = useCallback(async (values: any, config: Record<string, string>): Promise<string[]> => {
+    const extraBuckets: BucketInfo[] = values.protocolType === 's3' ? values.extraBuckets || [] : [];
+    const ids: string[] = [];
+    for (const bucket of extraBuckets) {
+      const bucketConfig = { ...config, bucket: bucket.name, ...(bucket.region ? { region: bucket.region } : {}) };
+      const result = await ApiService.addConnection(`${values.name} (${bucket.name})`, 's3', bucketConfig);
+      if (result?.id) ids.push(result.id);
+    }
+    return ids;
+  }, []);
+
   // 统一的连接操作处理
   const handleConnectionOperation = useCallback(async (values: any) => {
     const { type, connection } = modalConfig;
@@ -76,17 +89,22 @@ export const useConnectionOperations = (
       
       // 检查并创建 S3 bucket（编辑时不需要）
       if (type !== MODAL_TYPES.EDIT) {
-        const bucketReady = await checkAndCreateS3Bucket(values);
+        const bucketReady = await checkAndCreateS3Bucket(values, config);
         if (!bucketReady) return;
       }
+      await detectS3AddressingStyle(values, config);
 
       // 执行对应的操作
       let newConnectionId: string | null = null;
+      let extraConnectionIds: string[] = [];
       
       switch (type) {
         case MODAL_TYPES.ADD:
-          const addResult = await ApiService.addConnection(values.name, values.protocolType, config);
+          const hasExtraBuckets = values.protocolType === 's3' && values.extraBuckets?.length > 0;
+          const addName = hasExtraBuckets ? `${values.name} (${values.bucket})` : values.name;
+          const addResult = await ApiService.addConnection(addName, values.protocolType, config);
           newConnectionId = addResult?.id || `conn_${Date.now()}`;
+          extraConnectionIds = await addExtraS3Connections(values, config);
           message.success(i18nConnection.messages.addSuccess);
           break;
         case MODAL_TYPES.COPY:
@@ -109,7 +127,7 @@ export const useConnectionOperations = (
             if (dir.id === values.directoryId) {
               return {
                 ...dir,
-                connectionIds: [...dir.connectionIds, newConnectionId]
+                connectionIds: [...dir.connectionIds, newConnectionId, ...extraConnectionIds]
               };
             }
             return dir;
@@ -156,7 +174,9 @@ export const useConnectionOperations = (
     saveDirectories, 
     onConnectionsChange, 
     closeModal, 
-    checkAndCreateS3Bucket
+    checkAndCreateS3Bucket,
+    detectS3AddressingStyle,
+    addExtraS3Connections
   ]);
 
   // 删除连接处理
@@ -13,6 +13,8 @@ export const getConnectionIcon = (protocolType: string) => {
       return <DatabaseOutlined style={{ color: '#1890ff' }} />;
     case 'sftp':
       return <DatabaseOutlined style={{ color: '#722ed1' }} />;
+    case 'webdav':
+      return <CloudOutlined style={{ color: '#13c2c2' }} />;
     default:
       return <DatabaseOutlined style={{ color: '#8c8c8c' }} />;
   }
@@ -28,8 +30,20 @@ export const buildConfig = (values: any): Record<string, string> => {
     config.bucket = values.bucket;
     config.region = values.region;
     config.endpoint = values.endpoint;
-    config.access_key = values.accessKey;
-    config.secret_key = values.secretKey;
+    config.addressing_style = values.addressingStyle || 'auto';
+    config.credential_source = values.credentialSource || 'static';
+    // 只有静态凭证需要把密钥保存到连接配置中
+    if (config.credential_source === 'static') {
+      config.access_key = values.accessKey;
+      config.secret_key = values.secretKey;
+      if (values.sessionToken) config.session_token = values.sessionToken;
+    } else if (config.credential_source === 'profile' && values.profile) {
+      config.profile = values.profile;
+    }
+    if (config.credential_source !== 'anonymous' && values.roleArn) {
+      config.role_arn = values.roleArn;
+      if (values.externalId) config.external_id = values.externalId;
+    }
   } else if (values.protocolType === 'fs') {
     config.root_dir = values.root_dir;
   } else if (values.protocolType === 'ftp') {
@@ -39,6 +53,17 @@ export const buildConfig = (values: any): Record<string, string> => {
     config.password = values.password;
     config.root_dir = values.root_dir || '/';
     config.secure = values.secure ? 'true' : 'false';
+  } else if (values.protocolType === 'webdav') {
+    config.endpoint = values.endpoint;
+    // 只保存所选认证方式需要的字段
+    if (values.webdavAuth === 'basic') {
+      config.username = values.username;
+      if (values.password) config.password = values.password;
+    } else if (values.webdavAuth === 'token') {
+      config.token = values.token;
+    }
+    if (values.root_dir) config.root_dir = values.root_dir;
+    if (values.disableCopy) config.disable_copy = 'true';
   }
   
   return config;
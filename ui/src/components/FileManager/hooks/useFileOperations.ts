@@ -6,6 +6,7 @@ import { ApiService } from '../../../services/api';
 import { useAppI18n } from '../../../i18n/hooks/useI18n';
 import { PaginatedFileList, LoadingMode } from '../types';
 import { PAGINATION_MODE_THRESHOLD } from '../constants';
+import { formatFileSize } from '../utils';
 
 /**
  * 文件操作相关的 Hook
@@ -90,6 +91,29 @@ export const useFileOperations = (
     }
   }, [loadFiles, onStateUpdate]);
 
+  // 执行传输任务，期间以消息形式展示实时进度
+  const withTransferProgress = useCallback(async (task: () => Promise<void>) => {
+    const key = `transfer-${Date.now()}`;
+    const unlisten = await ApiService.onTransferProgress((progress) => {
+      if (!connection || progress.connection_id !== connection.id) return;
+      const percent = progress.total > 0
+        ? Math.floor((progress.transferred / progress.total) * 100)
+        : 100;
+      message.loading({
+        key,
+        content: `${progress.path} ${percent}% (${formatFileSize(Math.round(progress.bytes_per_sec))}/s)`,
+        duration: 0,
+      });
+    });
+
+    try {
+      await task();
+    } finally {
+      unlisten();
+      message.destroy(key);
+    }
+  }, [connection]);
+
   // 上传文件
   const handleUpload = useCallback(async () => {
     if (!connection) return;
@@ -106,14 +130,14 @@ export const useFileOperations = (
           ? currentPath + fileName 
           : currentPath + '/' + fileName;
 
-        await ApiService.uploadFile(connection.id, selected, remotePath);
+        await withTransferProgress(() => ApiService.uploadFile(connection.id, selected, remotePath));
         message.success(fileManager.messages.uploadSuccess);
         loadFiles(currentPath, currentPage);
       }
     } catch (error) {
       message.error(`${fileManager.messages.uploadFailed}: ${error}`);
     }
-  }, [connection, currentPath, currentPage, loadFiles, fileManager.dialogs.selectFileToUpload, fileManager.messages.uploadSuccess, fileManager.messages.uploadFailed]);
+  }, [connection, currentPath, currentPage, loadFiles, withTransferProgress, fileManager.dialogs.selectFileToUpload, fileManager.messages.uploadSuccess, fileManager.messages.uploadFailed]);
 
   // 下载文件
   const handleDownload = useCallback(async (file: FileInfo) => {
@@ -126,13 +150,13 @@ export const useFileOperations = (
       });
 
       if (savePath) {
-        await ApiService.downloadFile(connection.id, file.path, savePath);
+        await withTransferProgress(() => ApiService.downloadFile(connection.id, file.path, savePath));
         message.success(fileManager.messages.downloadSuccess);
       }
     } catch (error) {
       message.error(`${fileManager.messages.downloadFailed}: ${error}`);
     }
-  }, [connection, fileManager.dialogs.selectSaveLocation, fileManager.messages.downloadSuccess, fileManager.messages.downloadFailed]);
+  }, [connection, withTransferProgress, fileManager.dialogs.selectSaveLocation, fileManager.messages.downloadSuccess, fileManager.messages.downloadFailed]);
 
   // 删除文件
   const handleDelete = useCallback(async (file: FileInfo) => {
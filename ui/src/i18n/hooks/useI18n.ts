@@ -105,6 +105,8 @@ export const useAppI18n = () => {
         protocolPlaceholder: t('connection.modal.protocolPlaceholder'),
         protocolS3: t('connection.modal.protocolS3'),
         protocolFs: t('connection.modal.protocolFs'),
+        protocolFtp: t('connection.modal.protocolFtp'),
+        protocolWebdav: t('connection.modal.protocolWebdav'),
         directoryLabel: t('connection.modal.directoryLabel'),
         directoryPlaceholder: t('connection.modal.directoryPlaceholder'),
       },
@@ -132,12 +134,31 @@ export const useAppI18n = () => {
         regionPlaceholder: t('connection.fields.regionPlaceholder'),
         endpoint: t('connection.fields.endpoint'),
         endpointPlaceholder: t('connection.fields.endpointPlaceholder'),
+        addressingStyle: t('connection.fields.addressingStyle'),
+        addressingStyleTooltip: t('connection.fields.addressingStyleTooltip'),
+        addressingStyleAuto: t('connection.fields.addressingStyleAuto'),
+        addressingStylePath: t('connection.fields.addressingStylePath'),
+        addressingStyleVirtualHost: t('connection.fields.addressingStyleVirtualHost'),
         accessKey: t('connection.fields.accessKey'),
         accessKeyRequired: t('connection.fields.accessKeyRequired'),
         accessKeyPlaceholder: t('connection.fields.accessKeyPlaceholder'),
         secretKey: t('connection.fields.secretKey'),
         secretKeyRequired: t('connection.fields.secretKeyRequired'),
         secretKeyPlaceholder: t('connection.fields.secretKeyPlaceholder'),
+        credentialSource: t('connection.fields.credentialSource'),
+        credentialSourceTooltip: t('connection.fields.credentialSourceTooltip'),
+        credentialSourceStatic: t('connection.fields.credentialSourceStatic'),
+        credentialSourceEnv: t('connection.fields.credentialSourceEnv'),
+        credentialSourceProfile: t('connection.fields.credentialSourceProfile'),
+        credentialSourceAnonymous: t('connection.fields.credentialSourceAnonymous'),
+        sessionToken: t('connection.fields.sessionToken'),
+        sessionTokenTooltip: t('connection.fields.sessionTokenTooltip'),
+        sessionTokenPlaceholder: t('connection.fields.sessionTokenPlaceholder'),
+        profile: t('connection.fields.profile'),
+        profileTooltip: t('connection.fields.profileTooltip'),
+        roleArn: t('connection.fields.roleArn'),
+        roleArnTooltip: t('connection.fields.roleArnTooltip'),
+        externalId: t('connection.fields.externalId'),
         host: t('connection.fields.host'),
         hostRequired: t('connection.fields.hostRequired'),
         hostPlaceholder: t('connection.fields.hostPlaceholder'),
@@ -160,6 +181,21 @@ export const useAppI18n = () => {
         rootDirectoryTooltip: t('connection.fields.rootDirectoryTooltip'),
         secureConnection: t('connection.fields.secureConnection'),
         secureConnectionTooltip: t('connection.fields.secureConnectionTooltip'),
+        webdavEndpointPlaceholder: t('connection.fields.webdavEndpointPlaceholder'),
+        webdavEndpointRequired: t('connection.fields.webdavEndpointRequired'),
+        webdavEndpointInvalid: t('connection.fields.webdavEndpointInvalid'),
+        webdavEndpointTooltip: t('connection.fields.webdavEndpointTooltip'),
+        webdavAuth: t('connection.fields.webdavAuth'),
+        webdavAuthBasic: t('connection.fields.webdavAuthBasic'),
+        webdavAuthToken: t('connection.fields.webdavAuthToken'),
+        webdavAuthAnonymous: t('connection.fields.webdavAuthAnonymous'),
+        webdavUsernameTooltip: t('connection.fields.webdavUsernameTooltip'),
+        token: t('connection.fields.token'),
+        tokenRequired: t('connection.fields.tokenRequired'),
+        tokenPlaceholder: t('connection.fields.tokenPlaceholder'),
+        webdavRootDirectoryPlaceholder: t('connection.fields.webdavRootDirectoryPlaceholder'),
+        disableCopy: t('connection.fields.disableCopy'),
+        disableCopyTooltip: t('connection.fields.disableCopyTooltip'),
       },
     },
     // 文件管理器相关文本
@@ -325,6 +361,11 @@ export const useAppI18n = () => {
       bucketCreateFailed: t('s3.bucketCreateFailed'),
       checkBucketFailed: t('s3.checkBucketFailed'),
       operationCancelled: t('s3.operationCancelled'),
+      discoverBuckets: t('s3.discoverBuckets'),
+      selectBuckets: t('s3.selectBuckets'),
+      noBucketsFound: t('s3.noBucketsFound'),
+      discoverFailed: t('s3.discoverFailed'),
+      extraBucketsHint: t('s3.extraBucketsHint'),
     },
     // 文件预览相关文本
     filePreview: {
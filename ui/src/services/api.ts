@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
-import { Connection, FileInfo, PaginatedFileList, ApiResponse } from '../types';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
+import { Connection, FileInfo, PaginatedFileList, ApiResponse, TransferProgress, TransferReport, BucketInfo, BucketHead } from '../types';
 
 // 检测是否在 Tauri 环境中
 const isTauriEnvironment = (): boolean => {
@@ -128,11 +129,7 @@ export class ApiService {
   }
 
   static async checkS3BucketExists(
-    bucket: string,
-    region: string,
-    endpoint: string | null,
-    accessKey: string,
-    secretKey: string
+    config: Record<string, string>
   ): Promise<boolean> {
     if (!isTauriEnvironment()) {
       console.warn('Not in Tauri environment, simulating bucket check');
@@ -140,13 +137,7 @@ export class ApiService {
     }
 
     try {
-      const response: ApiResponse<boolean> = await invoke('check_s3_bucket_exists', {
-        bucket,
-        region,
-        endpoint,
-        accessKey,
-        secretKey,
-      });
+      const response: ApiResponse<boolean> = await invoke('check_s3_bucket_exists', { config });
       if (response.success && response.data !== undefined) {
         return response.data;
       }
@@ -158,11 +149,7 @@ export class ApiService {
   }
 
   static async createS3Bucket(
-    bucket: string,
-    region: string,
-    endpoint: string | null,
-    accessKey: string,
-    secretKey: string
+    config: Record<string, string>
   ): Promise<boolean> {
     if (!isTauriEnvironment()) {
       console.warn('Not in Tauri environment, simulating bucket creation');
@@ -170,13 +157,7 @@ export class ApiService {
     }
 
     try {
-      const response: ApiResponse<boolean> = await invoke('create_s3_bucket', {
-        bucket,
-        region,
-        endpoint,
-        accessKey,
-        secretKey,
-      });
+      const response: ApiResponse<boolean> = await invoke('create_s3_bucket', { config });
       if (response.success && response.data !== undefined) {
         return response.data;
       }
@@ -187,6 +168,98 @@ export class ApiService {
     }
   }
 
+  static async listS3Buckets(config: Record<string, string>): Promise<BucketInfo[]> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, returning mock bucket list');
+      return Promise.resolve([{ name: 'demo-bucket', region: 'us-east-1' }]);
+    }
+
+    try {
+      const response: ApiResponse<BucketInfo[]> = await invoke('list_s3_buckets', { config });
+      if (response.success && response.data !== undefined) {
+        return response.data;
+      }
+      throw new Error(response.error || '列出 bucket 失败');
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`列出 bucket 失败: ${error}`);
+    }
+  }
+
+  static async discoverS3Buckets(config: Record<string, string>): Promise<BucketInfo[]> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, returning mock bucket list');
+      return Promise.resolve([{ name: 'demo-bucket', region: 'us-east-1' }]);
+    }
+
+    try {
+      const response: ApiResponse<BucketInfo[]> = await invoke('discover_s3_buckets', { config });
+      if (response.success && response.data !== undefined) {
+        return response.data;
+      }
+      throw new Error(response.error || '发现 bucket 失败');
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`发现 bucket 失败: ${error}`);
+    }
+  }
+
+  static async headS3Bucket(config: Record<string, string>, bucket: string): Promise<BucketHead> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, simulating bucket head');
+      return Promise.resolve({ status: 'not_found' });
+    }
+
+    try {
+      const response: ApiResponse<BucketHead> = await invoke('head_s3_bucket', { config, bucket });
+      if (response.success && response.data !== undefined) {
+        return response.data;
+      }
+      throw new Error(response.error || '查询 bucket 状态失败');
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`查询 bucket 状态失败: ${error}`);
+    }
+  }
+
+  static async deleteS3Bucket(config: Record<string, string>, bucket: string): Promise<boolean> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, simulating bucket deletion');
+      return Promise.resolve(true);
+    }
+
+    try {
+      const response: ApiResponse<boolean> = await invoke('delete_s3_bucket', { config, bucket });
+      if (response.success && response.data !== undefined) {
+        return response.data;
+      }
+      throw new Error(response.error || '删除 bucket 失败');
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`删除 bucket 失败: ${error}`);
+    }
+  }
+
+  static async detectS3AddressingStyle(
+    config: Record<string, string>
+  ): Promise<string> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, simulating addressing style detection');
+      return Promise.resolve(config.endpoint ? 'path' : 'virtual_host');
+    }
+
+    try {
+      const response: ApiResponse<string> = await invoke('detect_s3_addressing_style', { config });
+      if (response.success && response.data !== undefined) {
+        return response.data;
+      }
+      throw new Error(response.error || '探测 S3 寻址方式失败');
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`探测 S3 寻址方式失败: ${error}`);
+    }
+  }
+
   static async listFiles(connectionId: string, path: string): Promise<FileInfo[]> {
     if (!isTauriEnvironment()) {
       console.warn('Not in Tauri environment, returning mock file list');
@@ -256,6 +329,17 @@ export class ApiService {
     }
   }
 
+  // 订阅后端推送的上传/下载进度事件
+  static async onTransferProgress(
+    handler: (progress: TransferProgress) => void
+  ): Promise<UnlistenFn> {
+    if (!isTauriEnvironment()) {
+      return Promise.resolve(() => {});
+    }
+
+    return listen<TransferProgress>('transfer-progress', (event) => handler(event.payload));
+  }
+
   static async downloadFile(
     connectionId: string,
     remotePath: string,
@@ -301,6 +385,76 @@ export class ApiService {
     }
   }
 
+  static async copyFile(connectionId: string, sourcePath: string, targetPath: string): Promise<void> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, simulating file copy');
+      return Promise.resolve();
+    }
+
+    try {
+      const response: ApiResponse<boolean> = await invoke('copy_file', {
+        connectionId,
+        sourcePath,
+        targetPath,
+      });
+      if (!response.success) {
+        throw new Error(response.error || '复制文件失败');
+      }
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`复制文件失败: ${error}`);
+    }
+  }
+
+  static async renameFile(connectionId: string, sourcePath: string, targetPath: string): Promise<void> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, simulating file move');
+      return Promise.resolve();
+    }
+
+    try {
+      const response: ApiResponse<boolean> = await invoke('rename_file', {
+        connectionId,
+        sourcePath,
+        targetPath,
+      });
+      if (!response.success) {
+        throw new Error(response.error || '移动文件失败');
+      }
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`移动文件失败: ${error}`);
+    }
+  }
+
+  static async transferBetweenConnections(
+    sourceConnectionId: string,
+    sourcePath: string,
+    targetConnectionId: string,
+    targetPath: string
+  ): Promise<TransferReport> {
+    if (!isTauriEnvironment()) {
+      console.warn('Not in Tauri environment, simulating transfer');
+      return Promise.resolve({ results: [] });
+    }
+
+    try {
+      const response: ApiResponse<TransferReport> = await invoke('transfer_between_connections', {
+        sourceConnectionId,
+        sourcePath,
+        targetConnectionId,
+        targetPath,
+      });
+      if (!response.success || !response.data) {
+        throw new Error(response.error || '传输文件失败');
+      }
+      return response.data;
+    } catch (error) {
+      console.error('Tauri invoke error:', error);
+      throw new Error(`传输文件失败: ${error}`);
+    }
+  }
+
   static async createDirectory(connectionId: string, path: string): Promise<void> {
     if (!isTauriEnvironment()) {
       console.warn('Not in Tauri environment, simulating directory creation');
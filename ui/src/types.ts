@@ -26,4 +26,38 @@ export interface ApiResponse<T> {
   success: boolean;
   data?: T;
   error?: string;
+  code?: string | null;
+  params?: Record<string, string> | null;
+}
+
+export interface TransferProgress {
+  connection_id: string;
+  direction: 'upload' | 'download' | 'copy';
+  path: string;
+  transferred: number;
+  total: number;
+  bytes_per_sec: number;
+  eta_secs?: number | null;
+}
+
+export interface TransferResult {
+  source: string;
+  target: string;
+  size: number;
+  error?: string | null;
+}
+
+export interface TransferReport {
+  results: TransferResult[];
+}
+
+export interface BucketInfo {
+  name: string;
+  creation_date?: string | null;
+  region?: string | null;
+}
+
+export interface BucketHead {
+  status: 'accessible' | 'wrong_region' | 'forbidden' | 'not_found';
+  region?: string | null;
 }